@@ -1,78 +1,201 @@
-use std::fs;
+use std::{
+    fmt,
+    io::{self, Write},
+};
+
+use crate::{
+    check::CrossCheck,
+    dial::Dial,
+    generate::{self, Generator},
+    parser::{complete, lines, map, one_of, pair, uint},
+    solution::{Answer, Error, Example, Solution, Tracer},
+};
 
 enum Action {
     Left(u32),
     Right(u32),
 }
 
-fn part_1(actions: &[Action]) {
-    let mut count = 0;
-    let mut cur: i32 = 50;
-    for action in actions {
-        match action {
-            Action::Left(n) => cur -= *n as i32,
-            Action::Right(n) => cur += *n as i32,
+impl Action {
+    fn amount(&self) -> i64 {
+        match self {
+            Action::Left(n) => -(*n as i64),
+            Action::Right(n) => *n as i64,
         }
-        if cur % 100 == 0 {
-            count += 1;
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Left(n) => write!(f, "L{}", n),
+            Action::Right(n) => write!(f, "R{}", n),
         }
     }
+}
 
-    println!("Part 1: {}", count);
+/// Dial state after an action, with the cumulative counts of both parts.
+struct Step {
+    position: i64,
+    /// Times the dial was left at 0 after a rotation.
+    part1_hits: i64,
+    /// Times any click left the dial at 0.
+    part2_hits: i64,
 }
 
-fn part_2(actions: &[Action]) {
-    let mut count = 0;
-    let mut cur: i32 = 50;
-    for action in actions {
-        let cur_p = cur;
-        match action {
-            Action::Left(n) => cur -= *n as i32,
-            Action::Right(n) => cur += *n as i32,
-        }
+fn simulate(actions: &[Action]) -> Vec<Step> {
+    let mut dial = Dial::default();
+    let mut part1_hits = 0;
+    let mut part2_hits = 0;
 
-        // If previous position was 0 and current is negative, we do not cross 0
-        if cur_p == 0 && cur < 0 {
-            count -= 1;
-        }
+    actions
+        .iter()
+        .map(|action| {
+            part2_hits += dial.rotate(action.amount());
+            if dial.position() == 0 {
+                part1_hits += 1;
+            }
 
-        while cur < 0 {
-            cur += 100;
-            count += 1;
-        }
+            Step {
+                position: dial.position(),
+                part1_hits,
+                part2_hits,
+            }
+        })
+        .collect()
+}
 
-        if cur == 0 {
-            count += 1;
-        }
+/// Write the simulation as CSV, starting with the initial position as step 0.
+fn write_trace(actions: &[Action], steps: &[Step], out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "step,action,position,part1_hits,part2_hits")?;
+    writeln!(out, "0,,{},0,0", Dial::default().position())?;
 
-        while cur >= 100 {
-            cur -= 100;
-            count += 1;
-        }
+    for (i, (action, step)) in actions.iter().zip(steps).enumerate() {
+        writeln!(
+            out,
+            "{},{},{},{},{}",
+            i + 1,
+            action,
+            step.position,
+            step.part1_hits,
+            step.part2_hits
+        )?;
     }
 
-    println!("Part 2: {}", count);
+    Ok(())
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let contents = fs::read_to_string("assets/2025/day_1.input")?;
-    // println!("File contents:\n{}", contents);
+fn trace(input: &str, out: &mut dyn Write) -> Result<(), Error> {
+    let actions = parse(input)?;
+    write_trace(&actions, &simulate(&actions), out)?;
 
-    let actions: Vec<&str> = contents.lines().collect();
-    let actions = actions
-        .iter()
-        .map(|s| {
-            let t = s.split_at(1);
-            match t.0 {
-                "L" => t.1.parse::<u32>().map(Action::Left).map_err(|e| e.into()),
-                "R" => t.1.parse::<u32>().map(Action::Right).map_err(|e| e.into()),
-                _ => Err("Unknown action".into()),
+    Ok(())
+}
+
+fn parse(contents: &str) -> Result<Vec<Action>, Error> {
+    let action = map(
+        pair(one_of("LR"), uint()),
+        |(direction, n)| match direction {
+            'L' => Action::Left(n),
+            _ => Action::Right(n),
+        },
+    );
+    Ok(complete(lines(action), contents)?)
+}
+
+/// Reference for both parts: turn the dial one click at a time.
+fn count_clicks(input: &str) -> Result<(i64, i64), Error> {
+    let mut position = 50;
+    let mut part1_hits = 0;
+    let mut part2_hits = 0;
+
+    for action in parse(input)? {
+        let amount = action.amount();
+        for _ in 0..amount.abs() {
+            position = (position + amount.signum()).rem_euclid(100);
+            if position == 0 {
+                part2_hits += 1;
             }
-        })
-        .collect::<Result<Vec<Action>, Box<dyn std::error::Error>>>()?;
+        }
+        if position == 0 {
+            part1_hits += 1;
+        }
+    }
 
-    part_1(&actions);
-    part_2(&actions);
+    Ok((part1_hits, part2_hits))
+}
 
-    Ok(())
+static CROSS_CHECKS: &[CrossCheck] = &[
+    CrossCheck {
+        name: "part 1 click by click",
+        generate: generate::rotations,
+        shrink: None,
+        reference: |input| Ok(count_clicks(input)?.0.into()),
+        optimised: |input| Day1.part_1(input),
+    },
+    CrossCheck {
+        name: "part 2 click by click",
+        generate: generate::rotations,
+        shrink: None,
+        reference: |input| Ok(count_clicks(input)?.1.into()),
+        optimised: |input| Day1.part_2(input),
+    },
+];
+
+const EXAMPLE: &str = "\
+L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82
+";
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn part_1(&self, input: &str) -> Result<Answer, Error> {
+        let steps = simulate(&parse(input)?);
+        let count = steps.last().map_or(0, |step| step.part1_hits);
+
+        Ok(count.into())
+    }
+
+    fn part_2(&self, input: &str) -> Result<Answer, Error> {
+        let steps = simulate(&parse(input)?);
+        let count = steps.last().map_or(0, |step| step.part2_hits);
+
+        Ok(count.into())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            name: "example",
+            input: EXAMPLE,
+            part_1: Some("3"),
+            part_2: Some("6"),
+            solution: None,
+        }]
+    }
+
+    fn generator(&self) -> Option<Generator> {
+        Some(generate::rotations)
+    }
+
+    fn cross_checks(&self) -> &'static [CrossCheck] {
+        CROSS_CHECKS
+    }
+
+    /// The step-by-step CSV trace of the dial.
+    fn tracer(&self) -> Option<Tracer> {
+        Some(trace)
+    }
+
+    fn debug(&self, input: &str, out: &mut dyn Write) -> Result<(), Error> {
+        trace(input, out)
+    }
 }
@@ -1,6 +1,21 @@
 use std::{
-    collections::{BinaryHeap, HashSet, VecDeque},
-    fs::read_to_string,
+    cell::RefCell,
+    hash::Hash,
+    io::Write,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    check::CrossCheck,
+    generate::{self, Generator},
+    parallel,
+    parser::{
+        Cursor, ParseError, complete, delimited, lines, list, many1, map, one_of, preceded, tag,
+        try_map, uint,
+    },
+    progress::Progress,
+    search::{self, Limits, Search, StateSpace, Stats},
+    solution::{Answer, Error, Example, Solution},
 };
 
 type Machine = (Vec<bool>, Vec<Vec<usize>>, Vec<i32>);
@@ -11,175 +26,841 @@ struct Lights(Vec<bool>);
 #[derive(Hash, PartialEq, Debug, Clone, Eq)]
 struct Joltage(Vec<i32>);
 
-#[derive(Clone, Debug, PartialEq, Eq)]
-struct HeapItem {
-    estimated_cost: i32,
-    cost: i32,
-    joltage: Joltage,
+/// How the states of a machine are stored while searching. The searches
+/// keep every state they reach, so a compact state that hashes fast
+/// matters more than anything else.
+trait Encoding {
+    type State: Clone + Eq + Hash;
+
+    fn start(&self) -> Self::State;
+
+    fn is_goal(&self, state: &Self::State) -> bool;
+
+    fn buttons(&self) -> usize;
+
+    /// The state after pressing `button`, `None` if it overshoots the goal.
+    fn press(&self, state: &Self::State, button: usize) -> Option<Self::State>;
 }
 
-impl Ord for HeapItem {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.estimated_cost.cmp(&self.estimated_cost)
+/// An encoding of joltage counters, which A* estimates from their deficits.
+trait Counters: Encoding {
+    /// Replace `deficits` by how far each counter is below its target.
+    fn deficits(&self, state: &Self::State, deficits: &mut Vec<i32>);
+}
+
+/// The buttons of a machine, as the heuristics see them.
+pub struct Wiring<'a> {
+    pub buttons: &'a [Vec<usize>],
+    /// Most a single press raises each counter by: a button may list a
+    /// counter more than once.
+    pub increments: Vec<i32>,
+}
+
+impl<'a> Wiring<'a> {
+    pub fn new(buttons: &'a [Vec<usize>], counters: usize) -> Self {
+        let mut increments = vec![0; counters];
+        for button in buttons {
+            for &i in button {
+                let times = button.iter().filter(|&&j| j == i).count() as i32;
+                increments[i] = increments[i].max(times);
+            }
+        }
+        Wiring {
+            buttons,
+            increments,
+        }
+    }
+
+    /// The most presses any one counter needs, `None` if some counter with
+    /// a deficit is raised by no button.
+    fn max_presses(&self, deficits: &[i32]) -> Option<i32> {
+        deficits
+            .iter()
+            .zip(&self.increments)
+            .try_fold(0, |max, (&deficit, &increment)| {
+                match (deficit, increment) {
+                    (0, _) => Some(max),
+                    (_, 0) => None,
+                    (deficit, increment) => Some(max.max((deficit + increment - 1) / increment)),
+                }
+            })
     }
 }
 
-impl PartialOrd for HeapItem {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+/// A lower bound on the presses still needed to bring every counter to its
+/// target, given the deficits left. It must never overestimate, or A* could
+/// return more presses than needed. `None` means the target is unreachable.
+pub trait Heuristic: Sync {
+    fn name(&self) -> &'static str;
+
+    fn estimate(&self, wiring: &Wiring, deficits: &[i32]) -> Option<i32>;
+}
+
+/// The presses the counter with the largest deficit needs on its own, given
+/// how much a press raises it.
+pub struct MaxDeficit;
+
+impl Heuristic for MaxDeficit {
+    fn name(&self) -> &'static str {
+        "max deficit"
+    }
+
+    fn estimate(&self, wiring: &Wiring, deficits: &[i32]) -> Option<i32> {
+        wiring.max_presses(deficits)
     }
 }
 
-fn bfs_part_1(machine: &Machine) -> Option<i32> {
-    let mut queue = VecDeque::new();
-    let mut visited = HashSet::new();
+/// The total deficit over the size of the largest button, since a press
+/// lowers the total by at most that much.
+pub struct TotalOverLargest;
 
-    let initial_lights = Lights(vec![false; machine.0.len()]);
-    queue.push_back((initial_lights.clone(), 0));
-    visited.insert(initial_lights);
+impl Heuristic for TotalOverLargest {
+    fn name(&self) -> &'static str {
+        "total over largest"
+    }
 
-    while let Some((state, cost)) = queue.pop_front() {
-        if state.0 == machine.0 {
-            return Some(cost);
+    fn estimate(&self, wiring: &Wiring, deficits: &[i32]) -> Option<i32> {
+        let largest = wiring.buttons.iter().map(Vec::len).max().unwrap_or(0) as i32;
+        match deficits.iter().sum::<i32>() {
+            0 => Some(0),
+            _ if largest == 0 => None,
+            total => Some((total + largest - 1) / largest),
         }
+    }
+}
 
-        for action in &machine.1 {
-            let mut new_state = state.clone();
-            for &idx in action {
-                new_state.0[idx] = !new_state.0[idx];
-            }
+/// Only buttons whose counters all have a deficit can still be pressed. A
+/// counter that none of them raises can no longer reach its target;
+/// otherwise the better of the two bounds above over those buttons.
+pub struct CounterBounds;
 
-            if !visited.contains(&new_state) {
-                visited.insert(new_state.clone());
-                queue.push_back((new_state, cost + 1));
-            }
+impl Heuristic for CounterBounds {
+    fn name(&self) -> &'static str {
+        "counter bounds"
+    }
+
+    fn estimate(&self, wiring: &Wiring, deficits: &[i32]) -> Option<i32> {
+        let usable = wiring
+            .buttons
+            .iter()
+            .filter(|button| button.iter().all(|&i| deficits[i] > 0))
+            .collect::<Vec<&Vec<usize>>>();
+
+        let stuck = deficits
+            .iter()
+            .enumerate()
+            .any(|(i, &deficit)| deficit > 0 && !usable.iter().any(|b| b.contains(&i)));
+        if stuck {
+            return None;
         }
+
+        let largest = usable.iter().map(|button| button.len()).max().unwrap_or(1) as i32;
+        let max = wiring.max_presses(deficits)?;
+        let total = deficits.iter().sum::<i32>();
+        Some(max.max((total + largest - 1) / largest))
     }
+}
+
+/// Every heuristic, in the order `run --debug` compares them.
+pub static HEURISTICS: &[&dyn Heuristic] = &[&MaxDeficit, &TotalOverLargest, &CounterBounds];
 
-    None
+/// Lights as the bits of a `u64`, a press is a xor.
+struct LightMask {
+    target: u64,
+    buttons: Vec<u64>,
 }
 
-fn estimate_cost(joltage: &Joltage, target: &[i32]) -> i32 {
-    let mut max_diff = 0;
-    for (i, &j) in joltage.0.iter().enumerate() {
-        max_diff = max_diff.max(target[i] - j);
+impl LightMask {
+    fn new(machine: &Machine) -> Option<Self> {
+        if machine.0.len() > 64 {
+            return None;
+        }
+
+        let target = machine
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_, on)| **on)
+            .fold(0, |mask, (i, _)| mask | 1 << i);
+        let buttons = machine
+            .1
+            .iter()
+            .map(|button| button.iter().fold(0, |mask, i| mask ^ 1 << i))
+            .collect();
+
+        Some(LightMask { target, buttons })
     }
-    max_diff
 }
 
-fn a_star_part_2(machine: &Machine) -> Option<i32> {
-    let mut heap = BinaryHeap::new();
-    let mut visited = HashSet::new();
+impl Encoding for LightMask {
+    type State = u64;
 
-    let initial_joltage = Joltage(vec![0; machine.2.len()]);
-    let initial_estimated = estimate_cost(&initial_joltage, &machine.2);
-    heap.push(HeapItem {
-        estimated_cost: initial_estimated,
-        cost: 0,
-        joltage: initial_joltage.clone(),
-    });
-    visited.insert(initial_joltage);
+    fn start(&self) -> u64 {
+        0
+    }
+
+    fn is_goal(&self, state: &u64) -> bool {
+        *state == self.target
+    }
+
+    fn buttons(&self) -> usize {
+        self.buttons.len()
+    }
+
+    fn press(&self, state: &u64, button: usize) -> Option<u64> {
+        Some(state ^ self.buttons[button])
+    }
+}
+
+/// Lights as a vector, for machines with more than 64 lights.
+struct LightVec<'a>(&'a Machine);
+
+impl Encoding for LightVec<'_> {
+    type State = Lights;
+
+    fn start(&self) -> Lights {
+        Lights(vec![false; self.0.0.len()])
+    }
+
+    fn is_goal(&self, state: &Lights) -> bool {
+        state.0 == self.0.0
+    }
+
+    fn buttons(&self) -> usize {
+        self.0.1.len()
+    }
 
-    while let Some(item) = heap.pop() {
-        if item.joltage.0 == machine.2 {
-            return Some(item.cost);
+    fn press(&self, state: &Lights, button: usize) -> Option<Lights> {
+        let mut new_state = state.clone();
+        for &idx in &self.0.1[button] {
+            new_state.0[idx] = !new_state.0[idx];
         }
+        Some(new_state)
+    }
+}
 
-        for action in &machine.1 {
-            let mut new_joltage = item.joltage.clone();
-            let mut valid = true;
+/// Joltages as the digits of a mixed-radix `u128`: counter `i` counts from
+/// 0 to its target, so its radix is `target + 1`. A press then adds a
+/// constant, as long as no pressed counter is already at its target.
+struct PackedJoltage {
+    target: u128,
+    /// Value of one unit of each counter.
+    places: Vec<u128>,
+    radixes: Vec<u128>,
+    /// Target of each counter.
+    targets: Vec<u128>,
+    /// Amount each button adds, with the counters it increments and how many
+    /// times it lists each of them.
+    buttons: Vec<(u128, Vec<(usize, u128)>)>,
+}
 
-            for &idx in action {
-                new_joltage.0[idx] += 1;
-                if new_joltage.0[idx] > machine.2[idx] {
-                    valid = false;
-                    break;
+impl PackedJoltage {
+    /// `None` if the targets do not fit in a `u128`.
+    fn new(machine: &Machine) -> Option<Self> {
+        let mut places = vec![];
+        let mut radixes = vec![];
+        let mut place = 1_u128;
+        for &target in &machine.2 {
+            let radix = u128::try_from(target).ok()? + 1;
+            places.push(place);
+            radixes.push(radix);
+            place = place.checked_mul(radix)?;
+        }
+
+        let targets = machine.2.iter().map(|&t| t as u128).collect::<Vec<u128>>();
+        let target = targets.iter().zip(&places).map(|(d, p)| d * p).sum();
+        let buttons = machine
+            .1
+            .iter()
+            .map(|button| {
+                let mut counters: Vec<(usize, u128)> = vec![];
+                for &i in button {
+                    match counters.iter_mut().find(|(j, _)| *j == i) {
+                        Some((_, times)) => *times += 1,
+                        None => counters.push((i, 1)),
+                    }
                 }
-            }
+                (button.iter().map(|&i| places[i]).sum(), counters)
+            })
+            .collect();
 
-            if !valid {
-                continue;
-            }
+        Some(PackedJoltage {
+            target,
+            places,
+            radixes,
+            targets,
+            buttons,
+        })
+    }
+
+    fn digit(&self, state: u128, i: usize) -> u128 {
+        state / self.places[i] % self.radixes[i]
+    }
+}
+
+impl Encoding for PackedJoltage {
+    type State = u128;
+
+    fn start(&self) -> u128 {
+        0
+    }
+
+    fn is_goal(&self, state: &u128) -> bool {
+        *state == self.target
+    }
+
+    fn buttons(&self) -> usize {
+        self.buttons.len()
+    }
+
+    fn press(&self, state: &u128, button: usize) -> Option<u128> {
+        let (amount, counters) = &self.buttons[button];
+        // A counter past its target would carry into the next one
+        if counters
+            .iter()
+            .any(|&(i, times)| self.digit(*state, i) + times > self.targets[i])
+        {
+            return None;
+        }
+        Some(state + amount)
+    }
+}
+
+impl Counters for PackedJoltage {
+    fn deficits(&self, state: &u128, deficits: &mut Vec<i32>) {
+        deficits.clear();
+        deficits.extend(
+            (0..self.targets.len()).map(|i| (self.targets[i] - self.digit(*state, i)) as i32),
+        );
+    }
+}
+
+/// Joltages as a vector, when the targets are too large to pack.
+struct JoltageVec<'a>(&'a Machine);
+
+impl Encoding for JoltageVec<'_> {
+    type State = Joltage;
+
+    fn start(&self) -> Joltage {
+        Joltage(vec![0; self.0.2.len()])
+    }
+
+    fn is_goal(&self, state: &Joltage) -> bool {
+        state.0 == self.0.2
+    }
 
-            if !visited.contains(&new_joltage) {
-                visited.insert(new_joltage.clone());
-                let new_cost = item.cost + 1;
-                let estimated = new_cost + estimate_cost(&new_joltage, &machine.2);
-                heap.push(HeapItem {
-                    estimated_cost: estimated,
-                    cost: new_cost,
-                    joltage: new_joltage,
-                });
+    fn buttons(&self) -> usize {
+        self.0.1.len()
+    }
+
+    fn press(&self, state: &Joltage, button: usize) -> Option<Joltage> {
+        let mut new_joltage = state.clone();
+        for &idx in &self.0.1[button] {
+            new_joltage.0[idx] += 1;
+            if new_joltage.0[idx] > self.0.2[idx] {
+                return None;
             }
         }
+        Some(new_joltage)
     }
+}
 
-    None
+impl Counters for JoltageVec<'_> {
+    fn deficits(&self, state: &Joltage, deficits: &mut Vec<i32>) {
+        deficits.clear();
+        deficits.extend(self.0.2.iter().zip(&state.0).map(|(target, j)| target - j));
+    }
 }
 
-fn part_1(machines: &[Machine]) {
-    let total: i32 = machines.iter().map(|m| bfs_part_1(m).unwrap()).sum();
-    println!("Part 1: {}", total);
+/// Limits on the search of a single machine, so one hard machine cannot
+/// stall the whole input.
+#[derive(Debug, Clone, Copy)]
+pub struct Budget {
+    pub time: Option<Duration>,
+    /// Distinct joltage states visited.
+    pub states: Option<usize>,
 }
 
-fn part_2(machines: &[Machine]) {
-    let result: i32 = machines
-        .iter()
-        .enumerate()
-        .map(|(i, machine)| {
-            println!("Machine: {}", i);
-            a_star_part_2(machine).unwrap()
-        })
-        .sum();
-
-    println!("Part 2: {}", result);
-}
-
-fn main() {
-    let contents = read_to_string("assets/2025/day_10.input").unwrap();
-    // let contents = read_to_string("assets/2025/test.input").unwrap();
-
-    let machines = contents
-        .lines()
-        .map(|line| {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            let len = parts.len();
-
-            let diagram = parts[0]
-                .strip_prefix('[')
-                .unwrap()
-                .strip_suffix(']')
-                .unwrap()
-                .chars()
-                .map(|c| c == '#')
-                .collect();
-
-            let buttons = parts[1..len - 1]
-                .iter()
-                .map(|x| {
-                    x.strip_prefix('(')
-                        .unwrap()
-                        .strip_suffix(')')
-                        .unwrap()
-                        .split(',')
-                        .map(|y| y.parse::<usize>().unwrap())
-                        .collect()
-                })
-                .collect();
-
-            let joltage = parts[len - 1]
-                .strip_prefix('{')
-                .unwrap()
-                .strip_suffix('}')
-                .unwrap()
-                .split(',')
-                .map(|x| x.parse().unwrap())
-                .collect();
-
-            (diagram, buttons, joltage)
-        })
-        .collect::<Vec<Machine>>();
+impl Budget {
+    /// A visited state takes about 70 bytes, so 2 million of them stay near
+    /// 150 MB per machine, with one machine per thread at a time.
+    pub const DEFAULT: Budget = Budget {
+        time: Some(Duration::from_secs(60)),
+        states: Some(2_000_000),
+    };
 
-    part_1(&machines);
-    part_2(&machines);
+    /// This budget with the limits of `AOC_MACHINE_SECONDS` and
+    /// `AOC_MACHINE_STATES` when set, where 0 means unlimited.
+    pub fn from_env(self) -> Budget {
+        let limit = |name| std::env::var(name).ok()?.parse::<u64>().ok();
+
+        let mut budget = self;
+        if let Some(seconds) = limit("AOC_MACHINE_SECONDS") {
+            budget.time = (seconds > 0).then(|| Duration::from_secs(seconds));
+        }
+        if let Some(states) = limit("AOC_MACHINE_STATES") {
+            budget.states = (states > 0).then_some(states as usize);
+        }
+        budget
+    }
+
+    fn limits(&self) -> Limits {
+        Limits {
+            time: self.time,
+            states: self.states,
+        }
+    }
+}
+
+enum Outcome {
+    Solved(i32),
+    Infeasible,
+    OverBudget,
+}
+
+impl Outcome {
+    fn of<S>(search: &Search<S>) -> Outcome {
+        match search.outcome {
+            search::Outcome::Found { cost, .. } => Outcome::Solved(cost as i32),
+            search::Outcome::Unreachable => Outcome::Infeasible,
+            search::Outcome::Aborted => Outcome::OverBudget,
+        }
+    }
+}
+
+fn presses<E: Encoding>(encoding: &E, state: &E::State) -> impl Iterator<Item = (E::State, u64)> {
+    (0..encoding.buttons())
+        .filter_map(move |button| encoding.press(state, button).map(|next| (next, 1)))
+}
+
+/// A search where every press costs one, with no heuristic: part 1, and
+/// the reference for part 2.
+struct PressSearch<E>(E);
+
+impl<E: Encoding> StateSpace for PressSearch<E> {
+    type State = E::State;
+
+    fn start(&self) -> E::State {
+        self.0.start()
+    }
+
+    fn is_goal(&self, state: &E::State) -> bool {
+        self.0.is_goal(state)
+    }
+
+    fn successors(&self, state: &E::State) -> impl Iterator<Item = (E::State, u64)> {
+        presses(&self.0, state)
+    }
+}
+
+/// Part 2 as a search guided by a heuristic on the counter deficits.
+struct JoltageSearch<'a, E> {
+    encoding: E,
+    wiring: Wiring<'a>,
+    heuristic: &'a dyn Heuristic,
+    /// Reused for the deficits of every state estimated.
+    deficits: RefCell<Vec<i32>>,
+}
+
+impl<'a, E> JoltageSearch<'a, E> {
+    fn new(encoding: E, machine: &'a Machine, heuristic: &'a dyn Heuristic) -> Self {
+        JoltageSearch {
+            encoding,
+            wiring: Wiring::new(&machine.1, machine.2.len()),
+            heuristic,
+            deficits: RefCell::new(vec![]),
+        }
+    }
+}
+
+impl<E: Counters> StateSpace for JoltageSearch<'_, E> {
+    type State = E::State;
+
+    fn start(&self) -> E::State {
+        self.encoding.start()
+    }
+
+    fn is_goal(&self, state: &E::State) -> bool {
+        self.encoding.is_goal(state)
+    }
+
+    fn successors(&self, state: &E::State) -> impl Iterator<Item = (E::State, u64)> {
+        presses(&self.encoding, state)
+    }
+
+    fn heuristic(&self, state: &E::State) -> Option<u64> {
+        let mut deficits = self.deficits.borrow_mut();
+        self.encoding.deficits(state, &mut deficits);
+        self.heuristic
+            .estimate(&self.wiring, &deficits)
+            .map(|estimate| estimate as u64)
+    }
+}
+
+fn bfs_part_1(machine: &Machine) -> Outcome {
+    match LightMask::new(machine) {
+        Some(encoding) => Outcome::of(&search::bfs(&PressSearch(encoding), &Limits::NONE)),
+        None => Outcome::of(&search::bfs(&PressSearch(LightVec(machine)), &Limits::NONE)),
+    }
+}
+
+fn a_star_part_2(
+    machine: &Machine,
+    heuristic: &dyn Heuristic,
+    budget: &Budget,
+) -> (Outcome, Stats) {
+    fn solve<E: Counters>(space: JoltageSearch<E>, budget: &Budget) -> (Outcome, Stats) {
+        let search = search::astar(&space, &budget.limits());
+        (Outcome::of(&search), search.stats)
+    }
+
+    match PackedJoltage::new(machine) {
+        Some(encoding) => solve(JoltageSearch::new(encoding, machine, heuristic), budget),
+        None => solve(
+            JoltageSearch::new(JoltageVec(machine), machine, heuristic),
+            budget,
+        ),
+    }
+}
+
+/// Solve the machines in parallel and sum their answers. If some machine is
+/// infeasible or over budget, the error lists them with the partial total.
+fn total(
+    machines: &[Machine],
+    label: &'static str,
+    solve: impl Fn(&Machine) -> Outcome + Sync,
+) -> Result<i32, Error> {
+    let progress = Progress::new(label, machines.len());
+    let outcomes = parallel::map(machines, |machine| {
+        let outcome = solve(machine);
+        progress.tick();
+        outcome
+    });
+    progress.finish();
+
+    let mut total = 0;
+    let mut infeasible = vec![];
+    let mut over_budget = vec![];
+    for (i, outcome) in outcomes.iter().enumerate() {
+        let line = (i + 1).to_string();
+        match outcome {
+            Outcome::Solved(presses) => total += presses,
+            Outcome::Infeasible => infeasible.push(line),
+            Outcome::OverBudget => over_budget.push(line),
+        }
+    }
+
+    if infeasible.is_empty() && over_budget.is_empty() {
+        return Ok(total);
+    }
+
+    let mut report = format!(
+        "partial total {} from {} of {} machines",
+        total,
+        machines.len() - infeasible.len() - over_budget.len(),
+        machines.len()
+    );
+    let lines = |lines: &[String]| match lines {
+        [line] => format!("line {}", line),
+        lines => format!("lines {}", lines.join(", ")),
+    };
+    if !infeasible.is_empty() {
+        report.push_str(&format!("; infeasible on {}", lines(&infeasible)));
+    }
+    if !over_budget.is_empty() {
+        report.push_str(&format!("; over budget on {}", lines(&over_budget)));
+    }
+    Err(report.into())
+}
+
+fn part_1(machines: &[Machine]) -> Result<i32, Error> {
+    total(machines, "Lights", bfs_part_1)
+}
+
+fn part_2(machines: &[Machine], heuristic: &dyn Heuristic, budget: &Budget) -> Result<i32, Error> {
+    total(machines, "Joltage", |machine| {
+        a_star_part_2(machine, heuristic, budget).0
+    })
+}
+
+/// Reference for part 2: breadth first over the joltages, with no heuristic
+/// and no limit.
+fn bfs_part_2(input: &str) -> Result<Answer, Error> {
+    let machines = parse(input)?;
+    let total = total(&machines, "Joltage BFS", |machine| {
+        Outcome::of(&search::bfs(
+            &PressSearch(JoltageVec(machine)),
+            &Limits::NONE,
+        ))
+    })?;
+    Ok(total.into())
+}
+
+/// Part 2 with `heuristic` and no limit, to compare with [`bfs_part_2`].
+fn unlimited_part_2(input: &str, heuristic: &dyn Heuristic) -> Result<Answer, Error> {
+    let budget = Budget {
+        time: None,
+        states: None,
+    };
+    Ok(part_2(&parse(input)?, heuristic, &budget)?.into())
+}
+
+/// Part 1 with the lights as a vector, to compare with the bit mask.
+fn light_vec_part_1(input: &str) -> Result<Answer, Error> {
+    let machines = parse(input)?;
+    let total = total(&machines, "Lights vector", |machine| {
+        Outcome::of(&search::bfs(&PressSearch(LightVec(machine)), &Limits::NONE))
+    })?;
+    Ok(total.into())
+}
+
+/// Part 1 on both light encodings, then one part 2 check per entry of
+/// [`HEURISTICS`].
+static CROSS_CHECKS: &[CrossCheck] = &[
+    CrossCheck {
+        name: "part 1 light vector against bit mask",
+        generate: generate::small_machines,
+        shrink: None,
+        reference: light_vec_part_1,
+        optimised: |input| Ok(part_1(&parse(input)?)?.into()),
+    },
+    CrossCheck {
+        name: "part 2 breadth first against max deficit",
+        generate: generate::small_machines,
+        shrink: None,
+        reference: bfs_part_2,
+        optimised: |input| unlimited_part_2(input, &MaxDeficit),
+    },
+    CrossCheck {
+        name: "part 2 breadth first against total over largest",
+        generate: generate::small_machines,
+        shrink: None,
+        reference: bfs_part_2,
+        optimised: |input| unlimited_part_2(input, &TotalOverLargest),
+    },
+    CrossCheck {
+        name: "part 2 breadth first against counter bounds",
+        generate: generate::small_machines,
+        shrink: None,
+        reference: bfs_part_2,
+        optimised: |input| unlimited_part_2(input, &CounterBounds),
+    },
+];
+
+/// Solve part 2 with every heuristic, reporting the machines solved within
+/// the budget and the work done.
+fn compare_heuristics(
+    machines: &[Machine],
+    budget: &Budget,
+    out: &mut dyn Write,
+) -> Result<(), Error> {
+    for &heuristic in HEURISTICS {
+        let start = Instant::now();
+        let progress = Progress::new(heuristic.name(), machines.len());
+        let results = parallel::map(machines, |machine| {
+            let result = a_star_part_2(machine, heuristic, budget);
+            progress.tick();
+            result
+        });
+        progress.finish();
+
+        let solved = results
+            .iter()
+            .filter(|(outcome, _)| matches!(outcome, Outcome::Solved(_)))
+            .count();
+        let expanded = results.iter().map(|(_, stats)| stats.expanded).sum::<u64>();
+        let visited = results
+            .iter()
+            .map(|(_, stats)| stats.visited)
+            .sum::<usize>();
+        writeln!(
+            out,
+            "{:<20} solved {}/{}, {} states expanded, {} visited, {:.2?}",
+            heuristic.name(),
+            solved,
+            machines.len(),
+            expanded,
+            visited,
+            start.elapsed()
+        )?;
+    }
+
+    Ok(())
+}
+
+/// One machine, with every button wired to existing lights and one joltage
+/// per light.
+fn machine(cursor: &mut Cursor) -> Result<Machine, ParseError> {
+    let diagram = delimited(tag("["), many1(map(one_of(".#"), |c| c == '#')), tag("]"))(cursor)?;
+    let lights = diagram.len();
+
+    let button = try_map(
+        delimited(tag("("), list(uint(), tag(",")), tag(")")),
+        |button: Vec<usize>| match button.iter().find(|&&i| i >= lights) {
+            Some(i) => Err(format!(
+                "button wired to light {}, but there are only {} lights",
+                i, lights
+            )),
+            None => Ok(button),
+        },
+    );
+    let buttons = preceded(tag(" "), list(button, tag(" ")))(cursor)?;
+
+    let joltage = try_map(
+        delimited(tag("{"), list(uint(), tag(",")), tag("}")),
+        |joltage: Vec<i32>| match joltage.len() {
+            len if len == lights => Ok(joltage),
+            len => Err(format!("expected {} joltages, found {}", lights, len)),
+        },
+    );
+    let joltage = preceded(tag(" "), joltage)(cursor)?;
+
+    Ok((diagram, buttons, joltage))
+}
+
+fn parse(contents: &str) -> Result<Vec<Machine>, ParseError> {
+    complete(lines(machine), contents)
+}
+
+const EXAMPLE: &str = "\
+[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+";
+
+pub struct Day10 {
+    /// Default limits of part 2, overridden by the environment.
+    pub budget: Budget,
+    pub heuristic: &'static dyn Heuristic,
+}
+
+impl Solution for Day10 {
+    fn part_1(&self, input: &str) -> Result<Answer, Error> {
+        Ok(part_1(&parse(input)?)?.into())
+    }
+
+    fn part_2(&self, input: &str) -> Result<Answer, Error> {
+        let result = part_2(&parse(input)?, self.heuristic, &self.budget.from_env())?;
+        Ok(result.into())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            name: "example",
+            input: EXAMPLE,
+            part_1: Some("7"),
+            part_2: Some("33"),
+            solution: None,
+        }]
+    }
+
+    fn generator(&self) -> Option<Generator> {
+        Some(generate::machines)
+    }
+
+    fn cross_checks(&self) -> &'static [CrossCheck] {
+        CROSS_CHECKS
+    }
+
+    /// The heuristics of part 2 side by side.
+    fn debug(&self, input: &str, out: &mut dyn Write) -> Result<(), Error> {
+        compare_heuristics(&parse(input)?, &self.budget.from_env(), out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(input: &str) -> (usize, usize, String) {
+        let e = parse(input).unwrap_err();
+        (e.line, e.column, e.message)
+    }
+
+    #[test]
+    fn buttons_must_be_wired_to_existing_lights() {
+        assert_eq!(
+            parse_error("[.#] (1) {1,1}\n[.##.] (3) (1,7) {3,5,4,7}\n"),
+            (
+                2,
+                12,
+                "button wired to light 7, but there are only 4 lights".into()
+            )
+        );
+        assert_eq!(
+            parse_error("[.##.] (4) {3,5,4,7}\n"),
+            (
+                1,
+                8,
+                "button wired to light 4, but there are only 4 lights".into()
+            )
+        );
+    }
+
+    #[test]
+    fn heuristics_allow_for_counters_raised_twice_a_press() {
+        // Twice (0,0) and once (1): 3 presses, though counter 0 is 4 short
+        let input = "[..] (0,0) (1) {4,1}\n";
+        let machine = &parse(input).unwrap()[0];
+        let wiring = Wiring::new(&machine.1, machine.2.len());
+        assert_eq!(wiring.increments, [2, 1]);
+
+        for &heuristic in HEURISTICS {
+            let estimate = heuristic.estimate(&wiring, &machine.2).unwrap();
+            assert!(estimate <= 3, "{} estimates {}", heuristic.name(), estimate);
+            assert_eq!(
+                unlimited_part_2(input, heuristic).unwrap(),
+                3.into(),
+                "{}",
+                heuristic.name()
+            );
+        }
+
+        // Counter 1 has a deficit no button can make up
+        assert_eq!(MaxDeficit.estimate(&wiring, &[0, 1]), Some(1));
+        let unwired = Wiring::new(&machine.1[..1], 2);
+        assert_eq!(MaxDeficit.estimate(&unwired, &[0, 1]), None);
+    }
+
+    #[test]
+    fn a_light_listed_twice_is_toggled_twice() {
+        // (0,0) leaves light 0 off, so only light 1 can be lit
+        let input = "[#.] (0,0) (1) {2,1}\n";
+        assert!(part_1(&parse(input).unwrap()).is_err());
+        assert!(light_vec_part_1(input).is_err());
+        assert_eq!(
+            part_1(&parse("[.#] (0,0) (1) {2,1}\n").unwrap()).unwrap(),
+            1
+        );
+        assert_eq!(unlimited_part_2(input, &CounterBounds).unwrap(), 2.into());
+    }
+
+    #[test]
+    fn packed_joltage_counts_a_counter_listed_twice() {
+        let machine = &parse("[.#] (1,1) (0) {1,3}\n").unwrap()[0];
+        let packed = PackedJoltage::new(machine).unwrap();
+        let once = packed.press(&packed.start(), 0).unwrap();
+        assert_eq!(packed.digit(once, 1), 2);
+        // A second press would take counter 1 to 4, past its target
+        assert_eq!(packed.press(&once, 0), None);
+
+        // Counter 1 only rises two at a time, so 3 is out of reach
+        assert!(unlimited_part_2("[.#] (1,1) (0) {1,3}\n", &CounterBounds).is_err());
+        assert_eq!(
+            unlimited_part_2("[.#] (1,1) (0) {1,4}\n", &CounterBounds).unwrap(),
+            3.into()
+        );
+    }
+
+    #[test]
+    fn every_light_has_a_joltage() {
+        assert_eq!(
+            parse_error("[.##.] (3) (1,3) {3,5,4}\n"),
+            (1, 18, "expected 4 joltages, found 3".into())
+        );
+        assert_eq!(
+            parse_error("[.#] (0) {1,2,3}\n"),
+            (1, 10, "expected 2 joltages, found 3".into())
+        );
+    }
 }
@@ -1,15 +1,20 @@
-use std::fs;
+use crate::{
+    bigint::BigInt,
+    check::CrossCheck,
+    generate::{self, Generator},
+    parallel,
+    parser::{complete, list, map, pair, separated_pair, tag, uint, whitespace},
+    solution::{Answer, Error, Example, Solution},
+};
 
 struct Range {
     start: u128,
     end: u128,
 }
 
-fn part_1(ranges: &Vec<Range>) {
-    let mut result_num: u128 = 0;
-
-    for range in ranges {
-        println!("Parsed range: {} - {}", range.start, range.end);
+fn part_1(ranges: &[Range]) -> BigInt {
+    parallel::map(ranges, |range| {
+        let mut result_num = BigInt::zero();
 
         for num in range.start..=range.end {
             if num == 0 {
@@ -27,21 +32,19 @@ fn part_1(ranges: &Vec<Range>) {
 
             let divisor: u128 = 10_i32.pow(digit_num / 2) as u128;
             if num % divisor == (num / divisor) {
-                result_num = result_num.saturating_add(num);
-
-                println!("{}", num);
+                result_num += BigInt::from(num);
             }
         }
-    }
 
-    println!("Part 1: {}", result_num);
+        result_num
+    })
+    .into_iter()
+    .sum()
 }
 
-fn part_2(ranges: &Vec<Range>) {
-    let mut result_num: u128 = 0;
-
-    for range in ranges {
-        println!("Parsed range: {} - {}", range.start, range.end);
+fn part_2(ranges: &[Range]) -> BigInt {
+    parallel::map(ranges, |range| {
+        let mut result_num = BigInt::zero();
 
         for num in range.start..=range.end {
             if num == 0 {
@@ -84,38 +87,102 @@ fn part_2(ranges: &Vec<Range>) {
             }
 
             if repeated {
-                println!("{}", num);
-                result_num += num;
+                result_num += BigInt::from(num);
             }
         }
-    }
 
-    println!("Part 2: {}", result_num);
+        result_num
+    })
+    .into_iter()
+    .sum()
+}
+
+fn parse(contents: &str) -> Result<Vec<Range>, Error> {
+    let range = map(separated_pair(uint(), tag("-"), uint()), |(start, end)| {
+        Range { start, end }
+    });
+    Ok(complete(
+        list(range, pair(tag(","), whitespace())),
+        contents,
+    )?)
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let contents = fs::read_to_string("assets/2025/day_2.input")?;
-    // let contents = fs::read_to_string("assets/2025/test.input")?;
-    println!("File contents:\n{}", contents);
-
-    let ranges: Vec<Range> = contents
-        .split(',')
-        .map(|part| {
-            let bounds: Vec<&str> = part.trim().split('-').collect();
-            if bounds.len() == 2 {
-                let start: u128 = bounds[0].parse().unwrap();
-                let end: u128 = bounds[1].parse().unwrap();
-                let range = Range { start, end };
-                Ok(range)
-            } else {
-                Err("Invalid range format")
+/// Whether `id` is a sequence of digits repeated at least twice, or exactly
+/// twice if `twice`.
+fn is_repeated(id: &str, twice: bool) -> bool {
+    (1..id.len())
+        .filter(|len| id.len().is_multiple_of(*len))
+        .filter(|len| !twice || id.len() == 2 * len)
+        .any(|len| id == id[..len].repeat(id.len() / len))
+}
+
+/// Reference for both parts: check every id as text.
+fn sum_as_text(input: &str, twice: bool) -> Result<Answer, Error> {
+    let ranges = parse(input)?;
+    // Shrinking can turn a range into a huge one, too slow to check, so the
+    // cross-check skips it
+    if ranges.iter().any(|range| range.end > range.start + 100_000) {
+        return Err("Range too wide to check".into());
+    }
+
+    let mut total = BigInt::zero();
+    for range in ranges {
+        for id in range.start..=range.end {
+            if is_repeated(&id.to_string(), twice) {
+                total += BigInt::from(id);
             }
-        })
-        .collect::<Result<Vec<_>, _>>()?;
+        }
+    }
+    Ok(total.into())
+}
+
+static CROSS_CHECKS: &[CrossCheck] = &[
+    CrossCheck {
+        name: "part 1 ids as text",
+        generate: generate::product_id_ranges,
+        shrink: None,
+        reference: |input| sum_as_text(input, true),
+        optimised: |input| Day2.part_1(input),
+    },
+    CrossCheck {
+        name: "part 2 ids as text",
+        generate: generate::product_id_ranges,
+        shrink: None,
+        reference: |input| sum_as_text(input, false),
+        optimised: |input| Day2.part_2(input),
+    },
+];
+
+const EXAMPLE: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\
+1698522-1698528,446443-446449,38593856-38593862,565653-565659,\
+824824821-824824827,2121212118-2121212124";
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn part_1(&self, input: &str) -> Result<Answer, Error> {
+        Ok(part_1(&parse(input)?).into())
+    }
+
+    fn part_2(&self, input: &str) -> Result<Answer, Error> {
+        Ok(part_2(&parse(input)?).into())
+    }
 
-    part_1(&ranges);
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            name: "example",
+            input: EXAMPLE,
+            part_1: Some("1227775554"),
+            part_2: Some("4174379265"),
+            solution: None,
+        }]
+    }
 
-    part_2(&ranges);
+    fn generator(&self) -> Option<Generator> {
+        Some(generate::product_id_ranges)
+    }
 
-    Ok(())
+    fn cross_checks(&self) -> &'static [CrossCheck] {
+        CROSS_CHECKS
+    }
 }
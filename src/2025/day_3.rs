@@ -1,83 +1,111 @@
-use std::fs;
-
-fn part_1(batteries: &Vec<&str>) {
-    let mut result: i64 = 0;
-    for battery in batteries {
-        println!("Battery: {}", battery);
+use std::io::Write;
+
+use crate::{
+    battery::{BankError, BatteryBank, parse_banks},
+    bigint::BigInt,
+    check::CrossCheck,
+    generate::{self, Generator},
+    solution::{Answer, Error, Example, Solution},
+};
+
+fn joltage(banks: &[BatteryBank], k: usize) -> Result<BigInt, BankError> {
+    let mut result = BigInt::zero();
+    for bank in banks {
+        result += bank.max_joltage(k)?.value();
+    }
+    Ok(result)
+}
 
-        let len = battery.len();
+/// Reference: `best[r]` is the largest number made of `r` batteries of the
+/// bank so far, updated battery by battery.
+fn joltage_dp(input: &str, k: usize) -> Result<Answer, Error> {
+    let mut result = 0u128;
+    for bank in parse_banks(input)? {
+        if bank.len() < k {
+            return Err(BankError::TooShort { len: bank.len(), k }.into());
+        }
 
-        let mut maximum = -1;
-        let mut second = -1;
-        for (i, ch) in battery.chars().enumerate() {
-            let digit = ch.to_digit(10).unwrap() as i64;
-            if digit > maximum && i != len - 1 {
-                maximum = digit;
-                second = -1;
-            } else if digit > second {
-                second = digit;
+        let mut best = vec![None; k + 1];
+        best[0] = Some(0u128);
+        for &digit in bank.digits() {
+            for r in (1..=k).rev() {
+                if let Some(prefix) = best[r - 1] {
+                    let candidate = prefix * 10 + digit as u128;
+                    best[r] = best[r].max(Some(candidate));
+                }
             }
         }
-
-        result += maximum * 10 + second;
-
-        println!("Number: {}", maximum * 10 + second);
+        result += best[k].unwrap_or(0);
     }
-    println!("Part 1: {}", result)
+    Ok(result.into())
 }
 
-fn part_2(batteries: &Vec<&str>) {
-    const SIZE: usize = 12;
-
-    let mut result: i64 = 0;
-    for battery in batteries {
-        println!("Battery: {}", battery);
-
-        let len = battery.len();
+static CROSS_CHECKS: &[CrossCheck] = &[
+    CrossCheck {
+        name: "part 1 dynamic programming",
+        generate: generate::battery_banks,
+        shrink: None,
+        reference: |input| joltage_dp(input, 2),
+        optimised: |input| Day3.part_1(input),
+    },
+    CrossCheck {
+        name: "part 2 dynamic programming",
+        generate: generate::battery_banks,
+        shrink: None,
+        reference: |input| joltage_dp(input, 12),
+        optimised: |input| Day3.part_2(input),
+    },
+];
+
+const EXAMPLE: &str = "\
+987654321111111
+811111111111119
+234234234234278
+818181911112111
+";
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn part_1(&self, input: &str) -> Result<Answer, Error> {
+        Ok(joltage(&parse_banks(input)?, 2)?.into())
+    }
 
-        let mut digits = [-1; SIZE];
+    fn part_2(&self, input: &str) -> Result<Answer, Error> {
+        Ok(joltage(&parse_banks(input)?, 12)?.into())
+    }
 
-        let mut index = 0;
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            name: "example",
+            input: EXAMPLE,
+            part_1: Some("357"),
+            part_2: Some("3121910778619"),
+            solution: None,
+        }]
+    }
 
-        for (ch_i, ch) in battery.chars().enumerate() {
-            let d = ch.to_digit(10).unwrap() as i64;
-            for j in (0..=index).rev() {
-                if d > digits[j] && ch_i <= len - SIZE + j {
-                    digits[j] = d;
+    fn generator(&self) -> Option<Generator> {
+        Some(generate::battery_banks)
+    }
 
-                    if j < SIZE - 1 {
-                        digits[j + 1] = -1;
-                    }
+    fn cross_checks(&self) -> &'static [CrossCheck] {
+        CROSS_CHECKS
+    }
 
-                    index = j;
-                }
+    /// The chosen batteries of every bank for both parts.
+    fn debug(&self, input: &str, out: &mut dyn Write) -> Result<(), Error> {
+        for bank in parse_banks(input)? {
+            for k in [2, 12] {
+                let selection = bank.max_joltage(k)?;
+                writeln!(
+                    out,
+                    "Number: {}, positions: {:?}",
+                    selection.value(),
+                    selection.positions
+                )?;
             }
-
-            index = (index + 1).min(SIZE - 1);
-        }
-
-        let mut total = 0;
-        for d in digits.iter() {
-            assert!(*d != -1);
-
-            total *= 10;
-            total += d;
         }
-
-        result += total;
-        println!("Number: {}", total);
+        Ok(())
     }
-    println!("Part 2: {}", result);
-}
-
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let contents = fs::read_to_string("assets/2025/day_3.input")?;
-    // let contents = fs::read_to_string("assets/2025/test.input")?;
-
-    let batteries: Vec<&str> = contents.lines().collect();
-
-    part_1(&batteries);
-    part_2(&batteries);
-
-    Ok(())
 }
@@ -1,140 +1,167 @@
-const EMPTY: char = '.';
-const PAPER: char = '@';
-const UNINITIALIZED: char = 'a';
-
-const DIRECTIONS: [(i32, i32); 8] = [
-    (-1, -1),
-    (-1, 0),
-    (-1, 1),
-    (0, -1),
-    (0, 1),
-    (1, -1),
-    (1, 0),
-    (1, 1),
-];
-
-fn remove(map: &mut [Vec<char>], counts: &mut [Vec<i32>]) -> i32 {
-    let row_num = counts.len();
-    let col_num = counts[0].len();
-
-    let mut results = 0;
-    for row_i in 0..row_num {
-        for col_i in 0..col_num {
-            if map[row_i][col_i] == PAPER && counts[row_i][col_i] < 4 {
-                results += 1;
-
-                counts[row_i][col_i] = 0;
-                map[row_i][col_i] = EMPTY;
-
-                for dir in &DIRECTIONS {
-                    let dst_row = row_i as i32 + dir.0;
-                    if dst_row < 0 || dst_row >= row_num as i32 {
-                        continue;
-                    }
-                    let dst_col = col_i as i32 + dir.1;
-                    if dst_col < 0 || dst_col >= col_num as i32 {
-                        continue;
-                    }
-
-                    counts[dst_row as usize][dst_col as usize] -= 1;
-                }
-            }
-        }
-    }
-
-    results
+use std::io::Write;
+
+use crate::{
+    accessibility::{Config, EMPTY, PAPER, Peeling, peel},
+    check::CrossCheck,
+    generate::{self, Generator},
+    parser::{complete, rectangular_grid},
+    solution::{Answer, Error, Example, Solution},
+};
+
+fn parse(contents: &str) -> Result<Vec<Vec<char>>, Error> {
+    let cell = |c| matches!(c, EMPTY | PAPER).then_some(c);
+    Ok(complete(rectangular_grid("'.' or '@'", cell), contents)?)
 }
 
-fn part_1(contents: &Vec<&str>) {
-    let row_num = contents.len();
-
-    assert!(row_num > 0);
-    let col_num = contents[0].len();
+fn peeling(contents: &str) -> Result<(Vec<Vec<char>>, Peeling), Error> {
+    let map = parse(contents)?;
+    let peeling = peel(&map, &Config::default());
 
-    let mut counts = vec![vec![0; col_num]; row_num];
-
-    for (row_i, row) in contents.iter().enumerate() {
-        for (col_i, ch) in row.chars().enumerate() {
-            if ch == EMPTY {
-                continue;
-            }
+    Ok((map, peeling))
+}
 
-            for dir in &DIRECTIONS {
-                let dst_row = row_i as i32 + dir.0;
-                if dst_row < 0 || dst_row >= row_num as i32 {
+/// Reference: rescan the whole grid every round and remove all accessible
+/// rolls at once. Returns the number removed in each round.
+fn remove_by_rescan(input: &str) -> Result<Vec<usize>, Error> {
+    let mut map = parse(input)?;
+    let rows = map.len() as i64;
+    let cols = map[0].len() as i64;
+    let mut rounds = vec![];
+
+    loop {
+        let mut accessible = vec![];
+        for r in 0..rows {
+            for c in 0..cols {
+                if map[r as usize][c as usize] != PAPER {
                     continue;
                 }
-                let dst_col = col_i as i32 + dir.1;
-                if dst_col < 0 || dst_col >= col_num as i32 {
-                    continue;
+                let neighbours = (-1..=1)
+                    .flat_map(|dr| (-1..=1).map(move |dc| (r + dr, c + dc)))
+                    .filter(|&(nr, nc)| (nr, nc) != (r, c))
+                    .filter(|&(nr, nc)| nr >= 0 && nr < rows && nc >= 0 && nc < cols)
+                    .filter(|&(nr, nc)| map[nr as usize][nc as usize] == PAPER)
+                    .count();
+                if neighbours < 4 {
+                    accessible.push((r as usize, c as usize));
                 }
-
-                counts[dst_row as usize][dst_col as usize] += 1;
             }
         }
-    }
 
-    let mut result = 0;
-    for (row_i, row) in contents.iter().enumerate() {
-        for (col_i, ch) in row.chars().enumerate() {
-            if ch == PAPER && counts[row_i][col_i] < 4 {
-                result += 1;
-            }
+        if accessible.is_empty() {
+            return Ok(rounds);
+        }
+        rounds.push(accessible.len());
+        for (r, c) in accessible {
+            map[r][c] = EMPTY;
         }
     }
-
-    println!("Part 1: {}", result);
 }
 
-fn part_2(contents: &Vec<&str>) {
-    let row_num = contents.len();
-
-    assert!(row_num > 0);
-    let col_num = contents[0].len();
-
-    let mut counts = vec![vec![0; col_num]; row_num];
-    let mut map = vec![vec![UNINITIALIZED; col_num]; row_num];
-
-    for (row_i, row) in contents.iter().enumerate() {
-        for (col_i, ch) in row.chars().enumerate() {
-            map[row_i][col_i] = ch;
-
-            if ch == EMPTY {
-                continue;
-            }
+/// The grid without one of its columns, for each column.
+fn remove_column(input: &str) -> Vec<String> {
+    let width = input.lines().map(|line| line.len()).max().unwrap_or(0);
+    (0..width)
+        .map(|c| {
+            input
+                .lines()
+                .map(|line| {
+                    let mut line = line.to_string();
+                    if c < line.len() {
+                        line.remove(c);
+                    }
+                    line + "\n"
+                })
+                .collect()
+        })
+        .collect()
+}
 
-            for dir in &DIRECTIONS {
-                let dst_row = row_i as i32 + dir.0;
-                if dst_row < 0 || dst_row >= row_num as i32 {
-                    continue;
-                }
-                let dst_col = col_i as i32 + dir.1;
-                if dst_col < 0 || dst_col >= col_num as i32 {
-                    continue;
-                }
+static CROSS_CHECKS: &[CrossCheck] = &[
+    CrossCheck {
+        name: "part 1 full rescan",
+        generate: generate::paper_grid,
+        shrink: Some(remove_column),
+        reference: |input| {
+            Ok(remove_by_rescan(input)?
+                .first()
+                .copied()
+                .unwrap_or(0)
+                .into())
+        },
+        optimised: |input| Day4.part_1(input),
+    },
+    CrossCheck {
+        name: "part 2 full rescan",
+        generate: generate::paper_grid,
+        shrink: Some(remove_column),
+        reference: |input| Ok(remove_by_rescan(input)?.iter().sum::<usize>().into()),
+        optimised: |input| Day4.part_2(input),
+    },
+];
 
-                counts[dst_row as usize][dst_col as usize] += 1;
-            }
-        }
+const EXAMPLE: &str = "\
+..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.
+";
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn part_1(&self, input: &str) -> Result<Answer, Error> {
+        let (_, peeling) = peeling(input)?;
+        Ok(peeling.removed_in_round(1).into())
     }
 
-    let mut result = 0;
-    let mut increase = remove(&mut map, &mut counts);
+    fn part_2(&self, input: &str) -> Result<Answer, Error> {
+        let (_, peeling) = peeling(input)?;
+        Ok(peeling.removed().into())
+    }
 
-    while increase > 0 {
-        println!("Removed {} papers", increase);
-        result += increase;
-        increase = remove(&mut map, &mut counts);
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            name: "example",
+            input: EXAMPLE,
+            part_1: Some("13"),
+            part_2: Some("43"),
+            solution: None,
+        }]
     }
 
-    println!("Part 2: {}", result);
-}
-fn main() {
-    let contents = std::fs::read_to_string("assets/2025/day_4.input").unwrap();
-    // let contents = std::fs::read_to_string("assets/2025/test.input").unwrap();
+    fn generator(&self) -> Option<Generator> {
+        Some(generate::paper_grid)
+    }
 
-    let contents = contents.lines().collect::<Vec<&str>>();
+    fn cross_checks(&self) -> &'static [CrossCheck] {
+        CROSS_CHECKS
+    }
 
-    part_1(&contents);
-    part_2(&contents);
+    /// Rolls removed per round, and how many survive.
+    fn debug(&self, input: &str, out: &mut dyn Write) -> Result<(), Error> {
+        let (map, peeling) = peeling(input)?;
+
+        for round in 1..=peeling.rounds() {
+            writeln!(
+                out,
+                "Round {}: removed {} papers",
+                round,
+                peeling.removed_in_round(round)
+            )?;
+        }
+        writeln!(
+            out,
+            "Removed in {} rounds, {} papers survive",
+            peeling.rounds(),
+            peeling.survivors(&map).len()
+        )?;
+
+        Ok(())
+    }
 }
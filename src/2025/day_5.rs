@@ -1,4 +1,8 @@
-use std::fs::read_to_string;
+use crate::{
+    generate::{self, Generator},
+    parser::{complete, lines, map, sections, separated_pair, tag, uint},
+    solution::{Answer, Error, Example, Solution},
+};
 
 #[derive(Debug)]
 struct Range {
@@ -6,7 +10,7 @@ struct Range {
     end: i128,
 }
 
-fn part_1(ranges: &[Range], ids: &[i128]) {
+fn part_1(ranges: &[Range], ids: &[i128]) -> i128 {
     let mut count = 0;
 
     for id in ids {
@@ -18,10 +22,10 @@ fn part_1(ranges: &[Range], ids: &[i128]) {
         }
     }
 
-    println!("Part 1: {}", count);
+    count
 }
 
-fn part_2(ranges: &mut [Range]) {
+fn part_2(ranges: &mut [Range]) -> i128 {
     ranges.sort_by_key(|x| x.start);
 
     let mut end_max = -1;
@@ -42,37 +46,54 @@ fn part_2(ranges: &mut [Range]) {
         }
     }
 
-    println!("Part 2: {}", count);
+    count
 }
 
-fn main() {
-    let contents = read_to_string("assets/2025/day_5.input").unwrap();
-    // let contents = read_to_string("assets/2025/test.input").unwrap();
-
-    let contents = contents.lines().collect::<Vec<&str>>();
-
-    let mut ranges = vec![];
-    let mut ids = vec![];
+fn parse(contents: &str) -> Result<(Vec<Range>, Vec<i128>), Error> {
+    let range = map(separated_pair(uint(), tag("-"), uint()), |(start, end)| {
+        Range { start, end }
+    });
+    Ok(complete(sections(lines(range), lines(uint())), contents)?)
+}
 
-    let mut parse_range = true;
+const EXAMPLE: &str = "\
+3-5
+10-14
+16-20
+12-18
+
+1
+5
+8
+11
+17
+32
+";
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn part_1(&self, input: &str) -> Result<Answer, Error> {
+        let (ranges, ids) = parse(input)?;
+        Ok(part_1(&ranges, &ids).into())
+    }
 
-    for content in contents {
-        if content.is_empty() {
-            parse_range = false;
-            continue;
-        }
+    fn part_2(&self, input: &str) -> Result<Answer, Error> {
+        let (mut ranges, _) = parse(input)?;
+        Ok(part_2(&mut ranges).into())
+    }
 
-        if parse_range {
-            let mut it = content.split('-');
-            ranges.push(Range {
-                start: it.next().unwrap().parse::<i128>().unwrap(),
-                end: it.next().unwrap().parse::<i128>().unwrap(),
-            });
-        } else {
-            ids.push(content.parse::<i128>().unwrap());
-        }
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            name: "example",
+            input: EXAMPLE,
+            part_1: Some("3"),
+            part_2: Some("14"),
+            solution: None,
+        }]
     }
 
-    part_1(&ranges, &ids);
-    part_2(&mut ranges);
+    fn generator(&self) -> Option<Generator> {
+        Some(generate::fresh_ranges)
+    }
 }
@@ -1,116 +1,136 @@
-use std::fs::read_to_string;
-
-fn part_1(contents: &[&str]) {
-    let len = contents.len();
-
-    let operands = contents[0..len - 1]
-        .iter()
-        .map(|&x| {
-            // println!("x: {}", x);
-            x.trim()
-                .split_ascii_whitespace()
-                .map(|x| x.parse::<i128>().unwrap())
-                .collect::<Vec<i128>>()
-        })
-        .collect::<Vec<Vec<i128>>>();
-
-    let operators = contents[len - 1]
-        .split_ascii_whitespace()
-        .collect::<Vec<&str>>();
-
-    let mut result = 0;
-
-    for i in 0..operators.len() {
-        match operators[i] {
-            "+" => {
-                let mut result_t = 0;
-                for operand in &operands {
-                    result_t += operand[i];
+use std::io::Write;
+
+use crate::{
+    bigint::BigInt,
+    generate::{self, Generator},
+    normalise::Normalisation,
+    solution::{Answer, Error, Example, Solution},
+    worksheet::{EvalError, Evaluation, Operators, Reading, evaluate, parse, total},
+};
+
+/// Exact value of a problem, recomputed with [`BigInt`] when `i128` overflowed.
+fn exact_value(evaluation: &Evaluation) -> Option<BigInt> {
+    match &evaluation.value {
+        Ok(value) => Some(BigInt::from(*value)),
+        Err(EvalError::Overflow) => {
+            let operands = evaluation.operands.iter().map(|x| BigInt::from(*x));
+            match evaluation.operator.as_str() {
+                "+" => Some(operands.sum()),
+                "*" => Some(operands.product()),
+                "-" => {
+                    let mut operands = operands;
+                    let first = operands.next()?;
+                    Some(operands.fold(first, |acc, x| acc - x))
                 }
-                result += result_t;
+                _ => None,
             }
-            "*" => {
-                let mut result_t = 1;
-
-                for operand in &operands {
-                    result_t *= operand[i];
-                }
-                result += result_t;
-            }
-            _ => panic!("Unknown operator: {}", operators[i]),
         }
+        Err(_) => None,
     }
-
-    println!("Part 1: {}", result);
 }
 
-fn part_2(contents: &[&str]) {
-    let len = contents.len();
-
-    let mut operands_new = vec![vec![]];
-    let len_operands = contents[0..len - 1].iter().map(|&x| x.len()).max().unwrap();
-    let mut operand_index = 0;
-
-    for i in 0..len_operands {
-        let mut operand = 0;
-        let mut zero_skip = 0;
-
-        for content in &contents[0..len - 1] {
-            let row = content.chars().nth(i).unwrap_or('0');
-
-            if row == ' ' {
-                zero_skip += 1;
-                continue;
-            } else {
-                if operand == 0 {
-                    operand = row.to_digit(10).unwrap() as i128;
-                } else {
-                    operand = operand * 10_i128.pow(zero_skip as u32 + 1)
-                        + row.to_digit(10).unwrap() as i128;
-                }
-                zero_skip = 0;
-            }
-        }
-        if operand == 0 {
-            operand_index += 1;
-            // println!("Current operands: {:?}", operands_new[operand_index - 1]);
-            continue;
-        }
+/// Grand total, falling back to exact arithmetic if it does not fit in `i128`.
+fn grand_total(evaluations: &[Evaluation]) -> Result<BigInt, EvalError> {
+    match total(evaluations) {
+        Ok(result) => Ok(BigInt::from(result)),
+        Err(EvalError::Overflow) => evaluations
+            .iter()
+            .map(exact_value)
+            .sum::<Option<BigInt>>()
+            .ok_or(EvalError::Overflow),
+        Err(e) => Err(e),
+    }
+}
 
-        if operands_new.len() <= operand_index {
-            operands_new.push(vec![]);
+fn solve(input: &str, reading: Reading) -> Result<Answer, Error> {
+    let evaluations = evaluate(&parse(input)?, reading, &Operators::default());
+
+    match grand_total(&evaluations) {
+        Ok(result) => Ok(result.into()),
+        Err(e) => {
+            let failed = evaluations
+                .iter()
+                .enumerate()
+                .filter_map(|(i, evaluation)| {
+                    let Err(e) = &evaluation.value else {
+                        return None;
+                    };
+                    Some(format!("problem {}: {}", i, e))
+                })
+                .collect::<Vec<String>>();
+            Err(format!("{} ({})", e, failed.join(", ")).into())
         }
+    }
+}
 
-        operands_new[operand_index].push(operand);
+// Trailing spaces are significant, hence the explicit line breaks
+const EXAMPLE: &str = concat!(
+    "123 328  51 64 \n",
+    " 45 64  387 23 \n",
+    "  6 98  215 314\n",
+    "*   +   *   +  \n",
+);
+
+/// `i128::MAX` taken from itself three times, below `i128::MIN`.
+const OVERFLOW: &str = concat!(
+    "170141183460469231731687303715884105727\n",
+    "170141183460469231731687303715884105727\n",
+    "170141183460469231731687303715884105727\n",
+    "170141183460469231731687303715884105727\n",
+    "-\n",
+);
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn part_1(&self, input: &str) -> Result<Answer, Error> {
+        solve(input, Reading::Rows)
     }
 
-    let operators = contents[len - 1]
-        .split_ascii_whitespace()
-        .collect::<Vec<&str>>();
+    fn part_2(&self, input: &str) -> Result<Answer, Error> {
+        solve(input, Reading::Columns)
+    }
 
-    let mut result = 0;
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                name: "example",
+                input: EXAMPLE,
+                part_1: Some("4277556"),
+                part_2: Some("3263827"),
+                solution: None,
+            },
+            Example {
+                name: "overflow",
+                input: OVERFLOW,
+                part_1: Some("-340282366920938463463374607431768211454"),
+                part_2: Some("-155540"),
+                solution: None,
+            },
+        ]
+    }
 
-    for i in 0..operators.len() {
-        match operators[i] {
-            "+" => {
-                result += operands_new[i].iter().sum::<i128>();
-            }
-            "*" => {
-                result += operands_new[i].iter().product::<i128>();
-            }
-            _ => panic!("Unknown operator: {}", operators[i]),
-        }
+    fn generator(&self) -> Option<Generator> {
+        Some(generate::worksheet)
     }
 
-    println!("Part 2: {}", result);
-}
+    /// Problems are told apart by their columns, so spaces are kept.
+    fn normalisation(&self) -> Normalisation {
+        Normalisation::LineEndings
+    }
 
-fn main() {
-    let contents = read_to_string("assets/2025/day_6.input").unwrap();
-    // let contents = read_to_string("assets/2025/test.input").unwrap();
+    /// Each problem's operands and value, for both readings.
+    fn debug(&self, input: &str, out: &mut dyn Write) -> Result<(), Error> {
+        let problems = parse(input)?;
+        let operators = Operators::default();
 
-    let contents = contents.lines().collect::<Vec<&str>>();
+        for reading in [Reading::Rows, Reading::Columns] {
+            writeln!(out, "{:?}:", reading)?;
+            for (i, evaluation) in evaluate(&problems, reading, &operators).iter().enumerate() {
+                writeln!(out, "Problem {}: {}", i, evaluation)?;
+            }
+        }
 
-    part_1(&contents);
-    part_2(&contents);
+        Ok(())
+    }
 }
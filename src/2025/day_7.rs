@@ -1,6 +1,10 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fs::read_to_string,
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    generate::{self, Generator},
+    memo::{Memo, MemoError},
+    parser::{complete, rectangular_grid},
+    solution::{Answer, Error, Example, Solution},
 };
 
 const START: char = 'S';
@@ -12,7 +16,7 @@ struct Position(i128, i128);
 #[derive(Debug)]
 struct Splitter(Position);
 
-fn part_1(start_pos: &Position, row_spliters_map: &HashMap<i128, Vec<Splitter>>) {
+fn part_1(start_pos: &Position, row_spliters_map: &HashMap<i128, Vec<Splitter>>) -> i128 {
     let mut result = 0;
 
     let mut buffer: Vec<Position> = vec![start_pos.clone()];
@@ -58,64 +62,58 @@ fn part_1(start_pos: &Position, row_spliters_map: &HashMap<i128, Vec<Splitter>>)
         hit_splitters.entry(pos_y).or_default().insert(-1);
     }
 
-    println!("Part 1: {}", result);
+    result
 }
 
-fn recursion(
+/// The first splitter a beam at `pos` meets going down, if any.
+fn next_splitter(
     pos: &Position,
-    map: &mut HashMap<Position, i128>,
     row_spliters_map: &HashMap<i128, Vec<Splitter>>,
-) -> i128 {
-    let mut result = 0;
-
-    let pos_x = pos.0;
-    let pos_y = pos.1;
-
-    if let Some(splitters) = row_spliters_map.get(&pos_y)
-        && let Some(splitter) = splitters.iter().find(|x| x.0.0 > pos_x)
-    {
-        // println!("  Next Splitter at: {:?}", splitter.0);
-        let splitter_pos = Position(splitter.0.0, splitter.0.1);
-        if map.contains_key(&splitter_pos) {
-            return *map.get(&splitter_pos).unwrap();
-        }
-
-        result += 1;
-
-        let left = Position(splitter.0.0 + 1, splitter.0.1 - 1);
-        result += recursion(&left, map, row_spliters_map);
-
-        let right = Position(splitter.0.0 + 1, splitter.0.1 + 1);
-        result += recursion(&right, map, row_spliters_map);
-
-        map.entry(splitter_pos).or_insert(result);
-    };
-
-    result
+) -> Option<Position> {
+    row_spliters_map
+        .get(&pos.1)?
+        .iter()
+        .find(|x| x.0.0 > pos.0)
+        .map(|splitter| splitter.0.clone())
 }
 
-fn part_2(start_pos: &Position, row_spliters_map: &HashMap<i128, Vec<Splitter>>) {
-    let mut result = 0;
-
-    result += recursion(start_pos, &mut HashMap::new(), row_spliters_map);
+fn part_2(
+    start_pos: &Position,
+    row_spliters_map: &HashMap<i128, Vec<Splitter>>,
+) -> Result<i128, MemoError<Position>> {
+    let Some(first) = next_splitter(start_pos, row_spliters_map) else {
+        return Ok(1);
+    };
 
-    result += 1; // Starting position
+    // Timelines a splitter adds: one for the split, plus those added further
+    // down on either side
+    let split = Memo::new().solve(first, |splitter, sub| {
+        let mut below = |dy| match next_splitter(
+            &Position(splitter.0 + 1, splitter.1 + dy),
+            row_spliters_map,
+        ) {
+            Some(next) => sub.get(&next),
+            None => Some(0),
+        };
+        let (left, right) = (below(-1), below(1));
+        Some(1 + left? + right?)
+    })?;
 
-    println!("Part 2: {}", result);
+    Ok(split + 1) // Starting position
 }
 
-fn main() {
-    let contents = read_to_string("assets/2025/day_7.input").unwrap();
-    // let contents = read_to_string("assets/2025/test.input").unwrap();
+type Manifold = (Position, HashMap<i128, Vec<Splitter>>);
 
-    let contents = contents.lines().collect::<Vec<&str>>();
+fn parse(contents: &str) -> Result<Manifold, Error> {
+    let cell = |c| matches!(c, '.' | START | SPLITTER).then_some(c);
+    let rows = complete(rectangular_grid("'.', 'S' or '^'", cell), contents)?;
 
     let mut col_spliters_map = HashMap::<i128, Vec<Splitter>>::new();
 
     let mut start_pos = Position(-1, -1);
 
-    for (x, content) in contents.iter().enumerate() {
-        for (y, c) in content.chars().enumerate() {
+    for (x, row) in rows.iter().enumerate() {
+        for (y, &c) in row.iter().enumerate() {
             if c == SPLITTER {
                 col_spliters_map
                     .entry(y as i128)
@@ -127,9 +125,56 @@ fn main() {
         }
     }
 
-    assert!(start_pos != Position(-1, -1), "Start position not found");
-    // println!("{:?}", row_spliters_map);
+    if start_pos == Position(-1, -1) {
+        return Err("Start position not found".into());
+    }
+
+    Ok((start_pos, col_spliters_map))
+}
+
+const EXAMPLE: &str = "\
+.......S.......
+...............
+.......^.......
+...............
+......^.^......
+...............
+.....^.^.^.....
+...............
+....^.^...^....
+...............
+...^.^...^.^...
+...............
+..^...^.....^..
+...............
+.^.^.^.^.^...^.
+...............
+";
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    fn part_1(&self, input: &str) -> Result<Answer, Error> {
+        let (start_pos, col_spliters_map) = parse(input)?;
+        Ok(part_1(&start_pos, &col_spliters_map).into())
+    }
+
+    fn part_2(&self, input: &str) -> Result<Answer, Error> {
+        let (start_pos, col_spliters_map) = parse(input)?;
+        Ok(part_2(&start_pos, &col_spliters_map)?.into())
+    }
 
-    part_1(&start_pos, &col_spliters_map);
-    part_2(&start_pos, &col_spliters_map);
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            name: "example",
+            input: EXAMPLE,
+            part_1: Some("21"),
+            part_2: Some("40"),
+            solution: None,
+        }]
+    }
+
+    fn generator(&self) -> Option<Generator> {
+        Some(generate::manifold)
+    }
 }
@@ -1,4 +1,9 @@
-use std::fs::read_to_string;
+use crate::{
+    check::CrossCheck,
+    generate::{self, Generator},
+    parser::{complete, int, lines, map, separated_pair, tag},
+    solution::{Answer, Error, Example, Solution},
+};
 
 #[derive(Clone)]
 struct Point {
@@ -6,70 +11,87 @@ struct Point {
     y: i64,
 }
 
-fn rectangle_in_polygon(p1_t: &Point, p2_t: &Point, points: &[Point]) -> bool {
-    let p1 = Point {
-        x: p1_t.x.min(p2_t.x),
-        y: p1_t.y.min(p2_t.y),
-    };
-    let p2 = Point {
-        x: p1_t.x.max(p2_t.x),
-        y: p1_t.y.max(p2_t.y),
-    };
-
-    let len = points.len();
-
-    for i in 0..len {
-        let j = (i + len - 1) % len;
-        let p_i = points[i].clone();
-        let p_j = points[j].clone();
-
-        assert!(p_i.x == p_j.x || p_i.y == p_j.y);
-
-        if p_i.x == p_j.x {
-            // Vertical edge
-            if p_i.x > p1.x && p_i.x < p2.x {
-                let y_min = p_i.y.min(p_j.y);
-                let y_max = p_i.y.max(p_j.y);
-
-                if !(p2.y <= y_min || p1.y >= y_max) {
-                    return false;
-                }
-            }
-        } else {
-            // p_i.y == p_j.y
-            // Horizontal edge
-            if p_i.y > p1.y && p_i.y < p2.y {
-                let x_min = p_i.x.min(p_j.x);
-                let x_max = p_i.x.max(p_j.x);
-
-                if !(p2.x <= x_min || p1.x >= x_max) {
-                    return false;
-                }
+/// Starts of the blocks that split one axis of the floor: a block for each
+/// coordinate of a red tile, one for each gap between them, and one more on
+/// either side. All tiles in a block of columns and a block of rows are alike.
+fn blocks(values: impl Iterator<Item = i64>) -> Vec<i64> {
+    let mut values = values.collect::<Vec<i64>>();
+    values.sort_unstable();
+    values.dedup();
+
+    let mut starts = vec![values[0] - 1];
+    for (i, &value) in values.iter().enumerate() {
+        starts.push(value);
+        if values.get(i + 1).is_none_or(|&next| next > value + 1) {
+            starts.push(value + 1);
+        }
+    }
+    starts
+}
+
+fn part_2(points: &[Point]) -> i64 {
+    let xs = blocks(points.iter().map(|p| p.x));
+    let ys = blocks(points.iter().map(|p| p.y));
+    let block = |starts: &[i64], value| starts.binary_search(&value).unwrap();
+
+    // Draw the loop, then flood the outside from the corner block
+    let mut outside = vec![vec![false; xs.len()]; ys.len()];
+    let mut on_loop = vec![vec![false; xs.len()]; ys.len()];
+    for (i, p) in points.iter().enumerate() {
+        let q = &points[(i + 1) % points.len()];
+        let (x1, x2) = (block(&xs, p.x.min(q.x)), block(&xs, p.x.max(q.x)));
+        let (y1, y2) = (block(&ys, p.y.min(q.y)), block(&ys, p.y.max(q.y)));
+        for row in &mut on_loop[y1..=y2] {
+            row[x1..=x2].fill(true);
+        }
+    }
+    let mut stack = vec![(0_usize, 0_usize)];
+    outside[0][0] = true;
+    while let Some((x, y)) = stack.pop() {
+        let neighbours = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+        for (nx, ny) in neighbours {
+            if nx < xs.len() && ny < ys.len() && !on_loop[ny][nx] && !outside[ny][nx] {
+                outside[ny][nx] = true;
+                stack.push((nx, ny));
             }
         }
     }
 
-    true
-}
+    // Outside blocks above and left of each block, so a rectangle of blocks
+    // is checked in constant time
+    let mut sums = vec![vec![0; xs.len() + 1]; ys.len() + 1];
+    for y in 0..ys.len() {
+        for x in 0..xs.len() {
+            sums[y + 1][x + 1] =
+                sums[y][x + 1] + sums[y + 1][x] - sums[y][x] + i32::from(outside[y][x]);
+        }
+    }
 
-fn part_2(points: &[Point]) {
     let mut max_area = 0;
 
     for (i, p1) in points.iter().enumerate() {
         for p2 in points.iter().skip(i + 1) {
-            if rectangle_in_polygon(p1, p2, points) {
-                let area = ((p2.x - p1.x).abs() + 1) * ((p2.y - p1.y).abs() + 1);
-                if area > max_area {
-                    max_area = area;
-                }
+            let area = ((p2.x - p1.x).abs() + 1) * ((p2.y - p1.y).abs() + 1);
+            if area <= max_area {
+                continue;
+            }
+            let (x1, x2) = (block(&xs, p1.x.min(p2.x)), block(&xs, p1.x.max(p2.x)) + 1);
+            let (y1, y2) = (block(&ys, p1.y.min(p2.y)), block(&ys, p1.y.max(p2.y)) + 1);
+            if sums[y2][x2] - sums[y1][x2] - sums[y2][x1] + sums[y1][x1] == 0 {
+                max_area = area;
             }
         }
     }
 
-    println!("Part 2: {}", max_area);
+    max_area
 }
 
-fn part_1(points: &[Point]) {
+fn part_1(points: &[Point]) -> i64 {
     let mut max_area = 0;
 
     for (i, p1) in points.iter().enumerate() {
@@ -81,24 +103,127 @@ fn part_1(points: &[Point]) {
         }
     }
 
-    println!("Part 1: {}", max_area);
+    max_area
 }
 
-fn main() {
-    let contents = read_to_string("assets/2025/day_9.input").unwrap();
-    // let contents = read_to_string("assets/2025/test.input").unwrap();
-
-    let points = contents
-        .lines()
-        .map(|line| {
-            let parts = line.split(',').collect::<Vec<&str>>();
-            Point {
-                x: parts[0].parse().unwrap(),
-                y: parts[1].parse().unwrap(),
-            }
+fn parse(contents: &str) -> Result<Vec<Point>, Error> {
+    let point = map(separated_pair(int(), tag(","), int()), |(x, y)| Point {
+        x,
+        y,
+    });
+    Ok(complete(lines(point), contents)?)
+}
+
+/// The red tiles as a loop, each in line with the next.
+fn polygon(contents: &str) -> Result<Vec<Point>, Error> {
+    let points = parse(contents)?;
+    for (i, p) in points.iter().enumerate() {
+        let j = (i + 1) % points.len();
+        let q = &points[j];
+        if p.x != q.x && p.y != q.y {
+            return Err(format!("Tiles on lines {} and {} are not in line", i + 1, j + 1).into());
+        }
+    }
+    Ok(points)
+}
+
+/// Whether the tile at `(x, y)` is on the loop or inside it.
+fn is_red_or_green(x: i64, y: i64, points: &[Point]) -> bool {
+    let mut crossings = 0;
+    for (i, p) in points.iter().enumerate() {
+        let q = &points[(i + 1) % points.len()];
+        let (y_min, y_max) = (p.y.min(q.y), p.y.max(q.y));
+        let (x_min, x_max) = (p.x.min(q.x), p.x.max(q.x));
+        if (x_min..=x_max).contains(&x) && (y_min..=y_max).contains(&y) {
+            return true;
+        }
+        // Vertical edges crossed going right, counting their lower end only
+        if p.x == q.x && p.x > x && y_min <= y && y < y_max {
+            crossings += 1;
+        }
+    }
+    crossings % 2 == 1
+}
+
+/// Reference for part 2: mark every tile of the floor, then check each
+/// rectangle tile by tile.
+fn scan_rectangles(input: &str) -> Result<Answer, Error> {
+    let points = polygon(input)?;
+    let min_x = points.iter().map(|p| p.x).min().unwrap();
+    let max_x = points.iter().map(|p| p.x).max().unwrap();
+    let min_y = points.iter().map(|p| p.y).min().unwrap();
+    let max_y = points.iter().map(|p| p.y).max().unwrap();
+
+    let tiles = (min_y..=max_y)
+        .map(|y| {
+            (min_x..=max_x)
+                .map(|x| is_red_or_green(x, y, &points))
+                .collect::<Vec<bool>>()
         })
-        .collect::<Vec<Point>>();
+        .collect::<Vec<Vec<bool>>>();
+    let tile = |x: i64, y: i64| tiles[(y - min_y) as usize][(x - min_x) as usize];
+
+    let mut max_area = 0;
+    for (i, p1) in points.iter().enumerate() {
+        for p2 in &points[i + 1..] {
+            let area = ((p2.x - p1.x).abs() + 1) * ((p2.y - p1.y).abs() + 1);
+            let xs = p1.x.min(p2.x)..=p1.x.max(p2.x);
+            if area > max_area
+                && (p1.y.min(p2.y)..=p1.y.max(p2.y)).all(|y| xs.clone().all(|x| tile(x, y)))
+            {
+                max_area = area;
+            }
+        }
+    }
 
-    part_1(&points);
-    part_2(&points);
+    Ok(max_area.into())
+}
+
+static CROSS_CHECKS: &[CrossCheck] = &[CrossCheck {
+    name: "part 2 tile scan",
+    generate: generate::rectilinear_loop,
+    shrink: None,
+    reference: scan_rectangles,
+    optimised: |input| Day9.part_2(input),
+}];
+
+const EXAMPLE: &str = "\
+7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3
+";
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    fn part_1(&self, input: &str) -> Result<Answer, Error> {
+        Ok(part_1(&parse(input)?).into())
+    }
+
+    fn part_2(&self, input: &str) -> Result<Answer, Error> {
+        Ok(part_2(&polygon(input)?).into())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            name: "example",
+            input: EXAMPLE,
+            part_1: Some("50"),
+            part_2: Some("24"),
+            solution: None,
+        }]
+    }
+
+    fn generator(&self) -> Option<Generator> {
+        Some(generate::rectilinear_loop)
+    }
+
+    fn cross_checks(&self) -> &'static [CrossCheck] {
+        CROSS_CHECKS
+    }
 }
@@ -0,0 +1,29 @@
+//! Solutions for Advent of Code 2025.
+
+use crate::solution::Solution;
+
+pub mod day_1;
+pub mod day_10;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+
+// One entry per line, kept in order by `main new`
+#[rustfmt::skip]
+pub static SOLUTIONS: &[(u32, &dyn Solution)] = &[
+    (1, &day_1::Day1),
+    (2, &day_2::Day2),
+    (3, &day_3::Day3),
+    (4, &day_4::Day4),
+    (5, &day_5::Day5),
+    (6, &day_6::Day6),
+    (7, &day_7::Day7),
+    (8, &day_8::Day8 { connections: 1000 }),
+    (9, &day_9::Day9),
+    (10, &day_10::Day10 { budget: day_10::Budget::DEFAULT, heuristic: &day_10::CounterBounds }),
+];
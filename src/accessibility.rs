@@ -0,0 +1,350 @@
+//! Accessibility analysis of paper rolls on a grid (2025 day 4).
+//!
+//! A roll is accessible when fewer than `threshold` of its neighbours hold a
+//! roll. Removing accessible rolls may make further rolls accessible, which is
+//! answered by [`peel`].
+
+use std::collections::VecDeque;
+
+pub const EMPTY: char = '.';
+pub const PAPER: char = '@';
+
+const FOUR_DIRECTIONS: [(i32, i32); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+const EIGHT_DIRECTIONS: [(i32, i32); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Which cells count as neighbours of a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// Orthogonal neighbours only.
+    Four,
+    /// Orthogonal and diagonal neighbours.
+    Eight,
+}
+
+impl Neighbourhood {
+    fn directions(&self) -> &'static [(i32, i32)] {
+        match self {
+            Neighbourhood::Four => &FOUR_DIRECTIONS,
+            Neighbourhood::Eight => &EIGHT_DIRECTIONS,
+        }
+    }
+}
+
+/// How neighbours beyond the edge of the grid are resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Boundary {
+    /// Cells outside the grid do not exist.
+    Bounded,
+    /// The grid wraps around on both axes (toroidal grid).
+    Wrapping,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    /// A roll is accessible when it has strictly fewer neighbouring rolls.
+    pub threshold: usize,
+    pub neighbourhood: Neighbourhood,
+    pub boundary: Boundary,
+}
+
+/// The puzzle rules: fewer than 4 of the 8 surrounding cells, no wrapping.
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            threshold: 4,
+            neighbourhood: Neighbourhood::Eight,
+            boundary: Boundary::Bounded,
+        }
+    }
+}
+
+impl Config {
+    /// The distinct cells around a cell, never the cell itself. On a small
+    /// wrapping grid several directions can lead to the same cell.
+    fn neighbours(
+        &self,
+        row_i: usize,
+        col_i: usize,
+        row_num: usize,
+        col_num: usize,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let boundary = self.boundary;
+        let directions = self.neighbourhood.directions();
+
+        let target = move |dir: &(i32, i32)| {
+            let dst_row = row_i as i32 + dir.0;
+            let dst_col = col_i as i32 + dir.1;
+
+            match boundary {
+                Boundary::Bounded => {
+                    if dst_row < 0 || dst_row >= row_num as i32 {
+                        return None;
+                    }
+                    if dst_col < 0 || dst_col >= col_num as i32 {
+                        return None;
+                    }
+                    Some((dst_row as usize, dst_col as usize))
+                }
+                Boundary::Wrapping => Some((
+                    dst_row.rem_euclid(row_num as i32) as usize,
+                    dst_col.rem_euclid(col_num as i32) as usize,
+                )),
+            }
+        };
+
+        directions.iter().enumerate().filter_map(move |(i, dir)| {
+            let cell = target(dir)?;
+            if boundary == Boundary::Wrapping
+                && (cell == (row_i, col_i)
+                    || directions[..i].iter().any(|d| target(d) == Some(cell)))
+            {
+                return None;
+            }
+            Some(cell)
+        })
+    }
+}
+
+/// Outcome of repeatedly removing every accessible roll until none is left.
+#[derive(Debug, Clone)]
+pub struct Peeling {
+    /// Round (starting from 1) in which the roll at each cell was removed,
+    /// `None` for empty cells and for rolls that survive.
+    removed_in: Vec<Vec<Option<usize>>>,
+    /// Rolls removed in each round, from round 1 to the last round that
+    /// removed any.
+    per_round: Vec<usize>,
+}
+
+impl Peeling {
+    /// Round in which the roll at the given cell was removed.
+    pub fn removed_in(&self, row_i: usize, col_i: usize) -> Option<usize> {
+        self.removed_in[row_i][col_i]
+    }
+
+    /// Number of rounds in which at least one roll was removed.
+    pub fn rounds(&self) -> usize {
+        self.per_round.len()
+    }
+
+    pub fn removed_in_round(&self, round: usize) -> usize {
+        round
+            .checked_sub(1)
+            .and_then(|i| self.per_round.get(i))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub fn removed(&self) -> usize {
+        self.per_round.iter().sum()
+    }
+
+    pub fn survivors(&self, map: &[Vec<char>]) -> Vec<(usize, usize)> {
+        let mut survivors = vec![];
+        for (row_i, row) in map.iter().enumerate() {
+            for (col_i, ch) in row.iter().enumerate() {
+                if *ch == PAPER && self.removed_in[row_i][col_i].is_none() {
+                    survivors.push((row_i, col_i));
+                }
+            }
+        }
+        survivors
+    }
+}
+
+fn count_neighbours(map: &[Vec<char>], config: &Config) -> Vec<Vec<usize>> {
+    let row_num = map.len();
+    let col_num = map[0].len();
+
+    let mut counts = vec![vec![0; col_num]; row_num];
+
+    for (row_i, row) in map.iter().enumerate() {
+        for (col_i, ch) in row.iter().enumerate() {
+            if *ch == EMPTY {
+                continue;
+            }
+
+            for (dst_row, dst_col) in config.neighbours(row_i, col_i, row_num, col_num) {
+                counts[dst_row][dst_col] += 1;
+            }
+        }
+    }
+
+    counts
+}
+
+/// Rolls that are accessible without removing anything.
+pub fn accessible(map: &[Vec<char>], config: &Config) -> Vec<(usize, usize)> {
+    assert!(!map.is_empty());
+
+    let counts = count_neighbours(map, config);
+
+    let mut result = vec![];
+    for (row_i, row) in map.iter().enumerate() {
+        for (col_i, ch) in row.iter().enumerate() {
+            if *ch == PAPER && counts[row_i][col_i] < config.threshold {
+                result.push((row_i, col_i));
+            }
+        }
+    }
+
+    result
+}
+
+/// Peel the grid like a k-core decomposition: only the neighbours of a removed
+/// roll are revisited, and a roll is queued for the next round as soon as its
+/// count drops below the threshold.
+pub fn peel(map: &[Vec<char>], config: &Config) -> Peeling {
+    assert!(!map.is_empty());
+
+    let row_num = map.len();
+    let col_num = map[0].len();
+
+    let mut counts = count_neighbours(map, config);
+    let mut removed_in = vec![vec![None; col_num]; row_num];
+    let mut queue = VecDeque::new();
+
+    for (row_i, col_i) in accessible(map, config) {
+        removed_in[row_i][col_i] = Some(1);
+        queue.push_back((row_i, col_i));
+    }
+
+    // Rounds are dequeued in order, so each one only ever extends the list
+    let mut per_round: Vec<usize> = vec![];
+
+    while let Some((row_i, col_i)) = queue.pop_front() {
+        let round: usize = removed_in[row_i][col_i].unwrap();
+        if per_round.len() < round {
+            per_round.push(0);
+        }
+        per_round[round - 1] += 1;
+
+        for (dst_row, dst_col) in config.neighbours(row_i, col_i, row_num, col_num) {
+            counts[dst_row][dst_col] -= 1;
+
+            if map[dst_row][dst_col] == PAPER
+                && removed_in[dst_row][dst_col].is_none()
+                && counts[dst_row][dst_col] < config.threshold
+            {
+                removed_in[dst_row][dst_col] = Some(round + 1);
+                queue.push_back((dst_row, dst_col));
+            }
+        }
+    }
+
+    Peeling {
+        removed_in,
+        per_round,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Vec<Vec<char>> {
+        rows.iter().map(|row| row.chars().collect()).collect()
+    }
+
+    fn config(threshold: usize, neighbourhood: Neighbourhood, boundary: Boundary) -> Config {
+        Config {
+            threshold,
+            neighbourhood,
+            boundary,
+        }
+    }
+
+    fn neighbours(
+        config: Config,
+        cell: (usize, usize),
+        size: (usize, usize),
+    ) -> Vec<(usize, usize)> {
+        let mut cells = config
+            .neighbours(cell.0, cell.1, size.0, size.1)
+            .collect::<Vec<_>>();
+        cells.sort();
+        cells
+    }
+
+    #[test]
+    fn four_neighbours_are_orthogonal() {
+        let four = config(4, Neighbourhood::Four, Boundary::Bounded);
+        assert_eq!(
+            neighbours(four, (1, 1), (3, 3)),
+            [(0, 1), (1, 0), (1, 2), (2, 1)]
+        );
+        assert_eq!(neighbours(four, (0, 0), (3, 3)), [(0, 1), (1, 0)]);
+
+        // The centre has four rolls around it, the corners only two
+        let map = grid(&["@@@", "@@@", "@@@"]);
+        let three = config(3, Neighbourhood::Four, Boundary::Bounded);
+        assert_eq!(accessible(&map, &three), [(0, 0), (0, 2), (2, 0), (2, 2)]);
+    }
+
+    #[test]
+    fn wrapping_reaches_across_the_edges() {
+        let wrapping = config(4, Neighbourhood::Eight, Boundary::Wrapping);
+        assert_eq!(neighbours(wrapping, (0, 0), (3, 3)).len(), 8);
+        assert!(neighbours(wrapping, (0, 0), (3, 3)).contains(&(2, 2)));
+
+        // Every roll of a full torus has 8 rolls around it
+        let map = grid(&["@@@@", "@@@@", "@@@@"]);
+        assert!(accessible(&map, &wrapping).is_empty());
+        let bounded = config(4, Neighbourhood::Eight, Boundary::Bounded);
+        assert_eq!(accessible(&map, &bounded), [(0, 0), (0, 3), (2, 0), (2, 3)]);
+    }
+
+    #[test]
+    fn small_wrapping_grids_count_each_neighbour_once() {
+        let eight = config(4, Neighbourhood::Eight, Boundary::Wrapping);
+        let four = config(4, Neighbourhood::Four, Boundary::Wrapping);
+
+        assert_eq!(neighbours(eight, (0, 0), (1, 1)), []);
+        assert_eq!(neighbours(four, (0, 0), (1, 2)), [(0, 1)]);
+        assert_eq!(neighbours(eight, (0, 0), (2, 2)), [(0, 1), (1, 0), (1, 1)]);
+
+        // A lone roll has no neighbours at all
+        assert_eq!(accessible(&grid(&["@"]), &eight), [(0, 0)]);
+
+        // Each of `@@` has the other as its only neighbour
+        let two = config(2, Neighbourhood::Four, Boundary::Wrapping);
+        let peeling = peel(&grid(&["@@"]), &two);
+        assert_eq!(peeling.removed(), 2);
+        assert_eq!(peeling.rounds(), 1);
+    }
+
+    #[test]
+    fn threshold_sets_how_crowded_is_too_crowded() {
+        // Counts of rolls around each roll: 1 2 2 1 along the row
+        let map = grid(&["@@@@"]);
+        let at = |threshold| config(threshold, Neighbourhood::Eight, Boundary::Bounded);
+
+        assert_eq!(accessible(&map, &at(1)), []);
+        assert_eq!(accessible(&map, &at(2)), [(0, 0), (0, 3)]);
+        assert_eq!(accessible(&map, &at(3)).len(), 4);
+
+        // With the ends gone, the middle two have one neighbour left
+        let peeling = peel(&map, &at(2));
+        assert_eq!(peeling.rounds(), 2);
+        assert_eq!(peeling.removed_in(0, 0), Some(1));
+        assert_eq!(peeling.removed_in(0, 1), Some(2));
+        assert_eq!(peeling.removed_in_round(1), 2);
+        assert_eq!(peeling.removed_in_round(2), 2);
+        assert_eq!(peeling.removed_in_round(3), 0);
+        assert_eq!(peeling.removed_in_round(0), 0);
+        assert_eq!(peeling.removed(), 4);
+
+        assert_eq!(peel(&map, &at(1)).removed(), 0);
+        assert_eq!(peel(&map, &at(1)).survivors(&map).len(), 4);
+    }
+}
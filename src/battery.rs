@@ -0,0 +1,170 @@
+//! Battery banks (2025 day 3): picking the batteries that give the largest
+//! joltage.
+
+use std::fmt;
+
+use crate::{
+    bigint::BigInt,
+    parser::{ParseError, complete, grid, map},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BankError {
+    TooShort { len: usize, k: usize },
+}
+
+impl fmt::Display for BankError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BankError::TooShort { len, k } => write!(
+                f,
+                "Battery bank has {} batteries, cannot pick {} of them",
+                len, k
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BankError {}
+
+/// A non-empty row of single-digit batteries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatteryBank {
+    digits: Vec<u8>,
+}
+
+impl BatteryBank {
+    pub fn digits(&self) -> &[u8] {
+        &self.digits
+    }
+
+    pub fn len(&self) -> usize {
+        self.digits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// The `k` batteries giving the largest joltage.
+    pub fn max_joltage(&self, k: usize) -> Result<Selection, BankError> {
+        largest_subsequence(&self.digits, k).ok_or(BankError::TooShort {
+            len: self.digits.len(),
+            k,
+        })
+    }
+}
+
+/// Parse one bank per line, reporting where the first invalid digit is.
+pub fn parse_banks(contents: &str) -> Result<Vec<BatteryBank>, ParseError> {
+    let digits = grid("a digit", |c| c.to_digit(10).map(|d| d as u8));
+    complete(
+        map(digits, |rows| {
+            rows.into_iter()
+                .map(|digits| BatteryBank { digits })
+                .collect()
+        }),
+        contents,
+    )
+}
+
+/// The chosen digits of a bank, in their original order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selection {
+    pub digits: Vec<u8>,
+    /// Index of each chosen digit in the bank.
+    pub positions: Vec<usize>,
+}
+
+impl Selection {
+    /// The chosen digits read as a decimal number.
+    pub fn value(&self) -> BigInt {
+        self.digits.iter().fold(BigInt::zero(), |acc, d| {
+            acc * BigInt::from(10_u8) + BigInt::from(*d)
+        })
+    }
+}
+
+/// Largest k-digit subsequence of `digits`, or `None` if there are fewer than
+/// `k` digits.
+///
+/// Uses a monotonic stack: a digit pops every smaller digit before it as long
+/// as enough digits remain to fill `k` places, so each digit is pushed and
+/// popped at most once.
+pub fn largest_subsequence(digits: &[u8], k: usize) -> Option<Selection> {
+    if digits.len() < k {
+        return None;
+    }
+
+    let mut drop = digits.len() - k;
+    let mut stack: Vec<usize> = Vec::with_capacity(digits.len());
+
+    for (i, d) in digits.iter().enumerate() {
+        while drop > 0 && stack.last().is_some_and(|top| digits[*top] < *d) {
+            stack.pop();
+            drop -= 1;
+        }
+        stack.push(i);
+    }
+    stack.truncate(k);
+
+    Some(Selection {
+        digits: stack.iter().map(|i| digits[*i]).collect(),
+        positions: stack,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reports_the_invalid_digit() {
+        let banks = parse_banks("123\n45\n").unwrap();
+        assert_eq!(banks[1].digits(), [4, 5]);
+
+        let e = parse_banks("123\n4x6\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 2));
+        assert_eq!(e.message, "expected a digit, found 'x6'");
+    }
+
+    #[test]
+    fn picks_nothing_or_everything() {
+        let digits = [3, 1, 4];
+
+        let none = largest_subsequence(&digits, 0).unwrap();
+        assert!(none.digits.is_empty() && none.positions.is_empty());
+        assert_eq!(none.value(), BigInt::zero());
+
+        let all = largest_subsequence(&digits, 3).unwrap();
+        assert_eq!(all.digits, [3, 1, 4]);
+        assert_eq!(all.positions, [0, 1, 2]);
+
+        assert_eq!(largest_subsequence(&digits, 4), None);
+        assert_eq!(largest_subsequence(&[], 1), None);
+    }
+
+    #[test]
+    fn keeps_the_earliest_of_equal_digits() {
+        let nines = largest_subsequence(&[9, 9, 9, 9], 2).unwrap();
+        assert_eq!(nines.digits, [9, 9]);
+        assert_eq!(nines.positions, [0, 1]);
+
+        let ties = largest_subsequence(&[1, 9, 1, 9, 1], 3).unwrap();
+        assert_eq!(ties.digits, [9, 9, 1]);
+        assert_eq!(ties.positions, [1, 3, 4]);
+    }
+
+    #[test]
+    fn reports_positions_of_the_chosen_digits() {
+        let digits = [8, 1, 8, 1, 8, 1, 9, 1, 1, 1, 1, 2, 1, 1, 1];
+        let selection = largest_subsequence(&digits, 2).unwrap();
+        assert_eq!(selection.digits, [9, 2]);
+        assert_eq!(selection.positions, [6, 11]);
+        assert_eq!(selection.value(), BigInt::from(92));
+
+        let selection = largest_subsequence(&digits, 4).unwrap();
+        assert_eq!(selection.digits, [9, 2, 1, 1]);
+        assert_eq!(selection.positions, [6, 11, 12, 13]);
+    }
+}
@@ -0,0 +1,440 @@
+//! A small arbitrary-precision signed integer for answers that do not fit in
+//! `i128`/`u128`.
+//!
+//! Only what puzzle answers need is provided: construction from primitive
+//! integers and strings, addition, subtraction, multiplication, comparison and
+//! decimal formatting.
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// Each limb holds 9 decimal digits, which keeps formatting trivial.
+const BASE: u64 = 1_000_000_000;
+const BASE_DIGITS: usize = 9;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigInt {
+    negative: bool,
+    /// Little-endian limbs without trailing zeros; zero has no limbs.
+    limbs: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> Self {
+        BigInt::default()
+    }
+
+    pub fn one() -> Self {
+        BigInt::from(1_u32)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt {
+            negative: false,
+            limbs: self.limbs.clone(),
+        }
+    }
+
+    /// Convert back to `i128` if the value fits.
+    pub fn to_i128(&self) -> Option<i128> {
+        let mut value: i128 = 0;
+        for limb in self.limbs.iter().rev() {
+            value = value.checked_mul(BASE as i128)?;
+            value = if self.negative {
+                value.checked_sub(*limb as i128)?
+            } else {
+                value.checked_add(*limb as i128)?
+            };
+        }
+        Some(value)
+    }
+
+    fn from_parts(negative: bool, mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigInt {
+            negative: negative && !limbs.is_empty(),
+            limbs,
+        }
+    }
+
+    fn from_magnitude(negative: bool, mut magnitude: u128) -> Self {
+        let mut limbs = vec![];
+        while magnitude > 0 {
+            limbs.push((magnitude % BASE as u128) as u32);
+            magnitude /= BASE as u128;
+        }
+        BigInt::from_parts(negative, limbs)
+    }
+}
+
+fn cmp_magnitude(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry = 0;
+    for i in 0..a.len().max(b.len()) {
+        let sum = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        result.push((sum % BASE) as u32);
+        carry = sum / BASE;
+    }
+    if carry > 0 {
+        result.push(carry as u32);
+    }
+    result
+}
+
+/// `a - b`, where `|a| >= |b|`.
+fn sub_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = 0;
+    for (i, x) in a.iter().enumerate() {
+        let mut diff = *x as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        if diff < 0 {
+            diff += BASE as i64;
+            borrow = 1;
+        } else {
+            borrow = 0;
+        }
+        result.push(diff as u32);
+    }
+    result
+}
+
+fn mul_magnitude(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+
+    let mut result = vec![0_u64; a.len() + b.len()];
+    for (i, x) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, y) in b.iter().enumerate() {
+            let cur = result[i + j] + *x as u64 * *y as u64 + carry;
+            result[i + j] = cur % BASE;
+            carry = cur / BASE;
+        }
+        let mut k = i + b.len();
+        while carry > 0 {
+            let cur = result[k] + carry;
+            result[k] = cur % BASE;
+            carry = cur / BASE;
+            k += 1;
+        }
+    }
+    result.into_iter().map(|x| x as u32).collect()
+}
+
+macro_rules! from_unsigned {
+    ($($t:ty),*) => {
+        $(impl From<$t> for BigInt {
+            fn from(value: $t) -> Self {
+                BigInt::from_magnitude(false, value as u128)
+            }
+        })*
+    };
+}
+
+macro_rules! from_signed {
+    ($($t:ty),*) => {
+        $(impl From<$t> for BigInt {
+            fn from(value: $t) -> Self {
+                BigInt::from_magnitude(value < 0, (value as i128).unsigned_abs())
+            }
+        })*
+    };
+}
+
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+from_signed!(i8, i16, i32, i64, i128, isize);
+
+impl FromStr for BigInt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("Invalid integer: '{}'", s));
+        }
+
+        let limbs = digits
+            .as_bytes()
+            .rchunks(BASE_DIGITS)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .fold(0_u32, |acc, b| acc * 10 + (b - b'0') as u32)
+            })
+            .collect();
+
+        Ok(BigInt::from_parts(negative, limbs))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((most, rest)) = self.limbs.split_last() else {
+            return f.pad_integral(true, "", "0");
+        };
+
+        let mut digits = most.to_string();
+        for limb in rest.iter().rev() {
+            digits.push_str(&format!("{:0width$}", limb, width = BASE_DIGITS));
+        }
+        f.pad_integral(!self.negative, "", &digits)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.limbs, &other.limbs),
+            (true, true) => cmp_magnitude(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.limbs)
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        -self.clone()
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_parts(self.negative, add_magnitude(&self.limbs, &other.limbs));
+        }
+
+        match cmp_magnitude(&self.limbs, &other.limbs) {
+            Ordering::Equal => BigInt::zero(),
+            Ordering::Greater => {
+                BigInt::from_parts(self.negative, sub_magnitude(&self.limbs, &other.limbs))
+            }
+            Ordering::Less => {
+                BigInt::from_parts(other.negative, sub_magnitude(&other.limbs, &self.limbs))
+            }
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &(-other)
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::from_parts(
+            self.negative != other.negative,
+            mul_magnitude(&self.limbs, &other.limbs),
+        )
+    }
+}
+
+macro_rules! forward_binop {
+    ($($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident);*) => {
+        $(
+            impl $trait for BigInt {
+                type Output = BigInt;
+
+                fn $method(self, other: BigInt) -> BigInt {
+                    (&self).$method(&other)
+                }
+            }
+
+            impl $trait<&BigInt> for BigInt {
+                type Output = BigInt;
+
+                fn $method(self, other: &BigInt) -> BigInt {
+                    (&self).$method(other)
+                }
+            }
+
+            impl $assign_trait for BigInt {
+                fn $assign_method(&mut self, other: BigInt) {
+                    *self = (&*self).$method(&other);
+                }
+            }
+
+            impl $assign_trait<&BigInt> for BigInt {
+                fn $assign_method(&mut self, other: &BigInt) {
+                    *self = (&*self).$method(other);
+                }
+            }
+        )*
+    };
+}
+
+forward_binop!(
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign
+);
+
+impl Sum for BigInt {
+    fn sum<I: Iterator<Item = BigInt>>(iter: I) -> Self {
+        iter.fold(BigInt::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<'a> Sum<&'a BigInt> for BigInt {
+    fn sum<I: Iterator<Item = &'a BigInt>>(iter: I) -> Self {
+        iter.fold(BigInt::zero(), |acc, x| acc + x)
+    }
+}
+
+impl Product for BigInt {
+    fn product<I: Iterator<Item = BigInt>>(iter: I) -> Self {
+        iter.fold(BigInt::one(), |acc, x| acc * x)
+    }
+}
+
+impl<'a> Product<&'a BigInt> for BigInt {
+    fn product<I: Iterator<Item = &'a BigInt>>(iter: I) -> Self {
+        iter.fold(BigInt::one(), |acc, x| acc * x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(s: &str) -> BigInt {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn carries_across_limbs() {
+        assert_eq!(big("999999999") + BigInt::one(), big("1000000000"));
+        assert_eq!(
+            big("999999999999999999") + BigInt::one(),
+            big("1000000000000000000")
+        );
+        assert_eq!(big("1000000000") - BigInt::one(), big("999999999"));
+        assert_eq!(
+            big("1000000000000000000") - BigInt::one(),
+            big("999999999999999999")
+        );
+        assert_eq!(
+            big("999999999") * big("999999999"),
+            big("999999998000000001")
+        );
+    }
+
+    #[test]
+    fn difference_with_itself_is_plain_zero() {
+        for s in [
+            "0",
+            "7",
+            "-7",
+            "1000000000",
+            "-123456789012345678901234567890",
+        ] {
+            let a = big(s);
+            let zero = &a - &a;
+            assert_eq!(zero, BigInt::zero());
+            assert!(!zero.is_negative());
+            assert_eq!(zero.to_string(), "0");
+        }
+        assert!(!(-BigInt::zero()).is_negative());
+        assert_eq!(big("-0"), BigInt::zero());
+        assert!(!(big("-5") * BigInt::zero()).is_negative());
+    }
+
+    #[test]
+    fn mixed_signs() {
+        assert_eq!(big("5") + big("-8"), big("-3"));
+        assert_eq!(big("-5") + big("8"), big("3"));
+        assert_eq!(big("8") + big("-5"), big("3"));
+        assert_eq!(big("-8") + big("5"), big("-3"));
+        assert_eq!(big("5") - big("-8"), big("13"));
+        assert_eq!(big("-5") - big("8"), big("-13"));
+        assert_eq!(big("-5") - big("-8"), big("3"));
+        assert_eq!(big("-1000000000") + big("1"), big("-999999999"));
+        assert_eq!(big("-3") * big("4"), big("-12"));
+        assert_eq!(big("-3") * big("-4"), big("12"));
+        assert!(big("-10") < big("-9"));
+        assert!(big("-1") < BigInt::zero());
+        assert!(big("1000000000") > big("999999999"));
+    }
+
+    #[test]
+    fn to_i128_round_trips() {
+        for n in [0, 1, -1, 999_999_999, -1_000_000_000, i128::MAX, i128::MIN] {
+            assert_eq!(BigInt::from(n).to_i128(), Some(n));
+        }
+        assert_eq!(BigInt::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(BigInt::from(u128::MAX).to_i128(), None);
+        assert_eq!((BigInt::from(i128::MAX) + BigInt::one()).to_i128(), None);
+        assert_eq!((BigInt::from(i128::MIN) - BigInt::one()).to_i128(), None);
+    }
+
+    #[test]
+    fn parses_and_formats() {
+        for s in [
+            "0",
+            "42",
+            "-42",
+            "1000000000",
+            "-1000000001",
+            "123456789012345678901234567890",
+            "-340282366920938463463374607431768211456",
+        ] {
+            assert_eq!(big(s).to_string(), s);
+        }
+        assert_eq!(big("+17").to_string(), "17");
+        assert_eq!(big("000000000000000012").to_string(), "12");
+        assert_eq!(format!("{:>6}", big("-12")), "   -12");
+        for s in ["", "-", "+", "1_000", "12a", " 1"] {
+            assert!(s.parse::<BigInt>().is_err(), "{:?}", s);
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Differential testing of an optimised solver against a reference one.
+//!
+//! Random inputs are generated until the two disagree, then the input is
+//! shrunk to a minimal one that still shows the disagreement. Inputs that the
+//! reference rejects are not compared, so shrinking never needs to know the
+//! input format, and a reference can decline inputs too slow to check.
+
+use crate::{
+    generate::Generator,
+    rng::Rng,
+    solution::{Answer, Error},
+};
+
+pub type Solver = fn(&str) -> Result<Answer, Error>;
+
+/// A reference and an optimised solver that must agree on every input.
+pub struct CrossCheck {
+    pub name: &'static str,
+    pub generate: Generator,
+    /// Smaller variants of an input tried while shrinking, on top of removing
+    /// lines and characters.
+    pub shrink: Option<fn(&str) -> Vec<String>>,
+    pub reference: Solver,
+    pub optimised: Solver,
+}
+
+/// A minimal input on which the solvers disagree.
+#[derive(Debug)]
+pub struct Mismatch {
+    pub seed: u64,
+    pub input: String,
+    pub reference: Result<String, String>,
+    pub optimised: Result<String, String>,
+}
+
+/// Why a cross-check failed.
+#[derive(Debug)]
+pub enum Failure {
+    /// The reference rejected every input, so nothing was compared.
+    NothingCompared,
+    Mismatch(Mismatch),
+}
+
+fn outcome(solver: Solver, input: &str) -> Result<String, String> {
+    solver(input)
+        .map(|answer| answer.to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// How the solvers fared on one input.
+enum Comparison {
+    /// The reference rejected the input.
+    Skipped,
+    Agree,
+    Disagree(Result<String, String>, Result<String, String>),
+}
+
+impl CrossCheck {
+    fn compare(&self, input: &str) -> Comparison {
+        let reference = outcome(self.reference, input);
+        if reference.is_err() {
+            return Comparison::Skipped;
+        }
+        let optimised = outcome(self.optimised, input);
+
+        if reference == optimised {
+            Comparison::Agree
+        } else {
+            Comparison::Disagree(reference, optimised)
+        }
+    }
+
+    fn candidates(&self, input: &str) -> Vec<String> {
+        let lines = input.lines().collect::<Vec<&str>>();
+        let join = |lines: &[&str]| lines.iter().map(|line| format!("{}\n", line)).collect();
+
+        let mut candidates = vec![];
+
+        // Remove chunks of lines, largest chunks first
+        let mut chunk = lines.len() / 2;
+        while chunk > 0 {
+            for start in (0..lines.len()).step_by(chunk) {
+                let mut rest = lines[..start].to_vec();
+                rest.extend_from_slice(&lines[(start + chunk).min(lines.len())..]);
+                candidates.push(join(&rest));
+            }
+            chunk /= 2;
+        }
+
+        if let Some(shrink) = self.shrink {
+            candidates.extend(shrink(input));
+        }
+
+        // Remove single characters
+        for (i, line) in lines.iter().enumerate() {
+            for (j, _) in line.char_indices() {
+                let mut shorter = line.to_string();
+                shorter.remove(j);
+                let mut rest = lines.clone();
+                rest[i] = &shorter;
+                candidates.push(join(&rest));
+            }
+        }
+
+        candidates
+    }
+
+    /// Greedily replace the input by a smaller one that still disagrees,
+    /// until no candidate does.
+    fn shrink(&self, mut input: String) -> String {
+        'outer: loop {
+            for candidate in self.candidates(&input) {
+                // Only strictly smaller inputs, so shrinking terminates
+                if (candidate.len(), &candidate) < (input.len(), &input)
+                    && matches!(self.compare(&candidate), Comparison::Disagree(..))
+                {
+                    input = candidate;
+                    continue 'outer;
+                }
+            }
+            return input;
+        }
+    }
+
+    /// Compare the solvers on `cases` random inputs of growing size, and
+    /// return how many the reference accepted.
+    pub fn run(&self, cases: usize, seed: u64) -> Result<usize, Failure> {
+        let mut rng = Rng::new(seed);
+        let mut compared = 0;
+
+        for case in 0..cases {
+            let size = 1 + case * 20 / cases.max(1);
+            let input = (self.generate)(&mut rng, size);
+
+            match self.compare(&input) {
+                Comparison::Skipped => {}
+                Comparison::Agree => compared += 1,
+                Comparison::Disagree(..) => {
+                    let input = self.shrink(input);
+                    let Comparison::Disagree(reference, optimised) = self.compare(&input) else {
+                        unreachable!("shrinking keeps a disagreeing input");
+                    };
+
+                    return Err(Failure::Mismatch(Mismatch {
+                        seed,
+                        input,
+                        reference,
+                        optimised,
+                    }));
+                }
+            }
+        }
+
+        match compared {
+            0 if cases > 0 => Err(Failure::NothingCompared),
+            _ => Ok(compared),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Lines of numbers below 100.
+    fn numbers(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| format!("{}\n", rng.range(0, 99)))
+            .collect()
+    }
+
+    fn sum(input: &str) -> Result<Answer, Error> {
+        let mut total = 0;
+        for line in input.lines() {
+            total += line.parse::<i64>()?;
+        }
+        Ok(total.into())
+    }
+
+    /// [`sum`] with a planted bug: it skips every number with a 7.
+    fn sum_without_sevens(input: &str) -> Result<Answer, Error> {
+        sum(&input
+            .lines()
+            .filter(|line| !line.contains('7'))
+            .map(|line| format!("{}\n", line))
+            .collect::<String>())
+    }
+
+    fn check(reference: Solver, optimised: Solver) -> CrossCheck {
+        CrossCheck {
+            name: "test",
+            generate: numbers,
+            shrink: None,
+            reference,
+            optimised,
+        }
+    }
+
+    #[test]
+    fn agreeing_solvers_compare_every_case() {
+        assert_eq!(check(sum, sum).run(30, 1).unwrap(), 30);
+    }
+
+    #[test]
+    fn a_planted_bug_is_shrunk_to_a_minimal_input() {
+        let Err(Failure::Mismatch(mismatch)) = check(sum, sum_without_sevens).run(30, 1) else {
+            panic!("the planted bug went unnoticed");
+        };
+        assert_eq!(mismatch.seed, 1);
+        assert_eq!(mismatch.input, "7\n");
+        assert_eq!(mismatch.reference, Ok("7".into()));
+        assert_eq!(mismatch.optimised, Ok("0".into()));
+    }
+
+    #[test]
+    fn inputs_the_reference_rejects_are_not_compared() {
+        // Only inputs with an even first number are accepted
+        let even = |input: &str| match input.lines().next().map(|line| line.parse::<i64>()) {
+            Some(Ok(first)) if first % 2 == 0 => sum(input),
+            _ => Err("odd".into()),
+        };
+        let compared = check(even, sum).run(30, 1);
+        assert!(
+            matches!(compared, Ok(n) if 0 < n && n < 30),
+            "{:?}",
+            compared
+        );
+
+        let Err(Failure::NothingCompared) =
+            check(|_| Err("no".into()), |_| Err("no".into())).run(30, 1)
+        else {
+            panic!("rejecting every input passed");
+        };
+    }
+}
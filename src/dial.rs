@@ -0,0 +1,127 @@
+//! The safe dial of 2025 day 1.
+
+/// A circular dial numbered `0..size`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dial {
+    size: i64,
+    position: i64,
+}
+
+/// The puzzle dial: 100 positions, starting at 50.
+impl Default for Dial {
+    fn default() -> Self {
+        Dial::new(100, 50)
+    }
+}
+
+impl Dial {
+    pub fn new(size: i64, start: i64) -> Self {
+        assert!(size > 0, "Dial size must be positive");
+
+        Dial {
+            size,
+            position: start.rem_euclid(size),
+        }
+    }
+
+    pub fn position(&self) -> i64 {
+        self.position
+    }
+
+    pub fn size(&self) -> i64 {
+        self.size
+    }
+
+    /// Rotate by `amount` clicks, to the right if positive and to the left if
+    /// negative, and return how many clicks left the dial pointing at 0.
+    ///
+    /// The count is computed in O(1) by counting the multiples of `size`
+    /// among the positions passed, so starting at 0 is not counted while
+    /// ending at 0 is.
+    pub fn rotate(&mut self, amount: i64) -> i64 {
+        let start = self.position;
+        let end = start + amount;
+
+        let hits = if amount >= 0 {
+            // Multiples of size in (start, end]
+            end.div_euclid(self.size) - start.div_euclid(self.size)
+        } else {
+            // Multiples of size in [end, start)
+            (start - 1).div_euclid(self.size) - (end - 1).div_euclid(self.size)
+        };
+
+        self.position = end.rem_euclid(self.size);
+
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaving_zero_does_not_count() {
+        let mut dial = Dial::new(100, 0);
+        assert_eq!(dial.rotate(-1), 0);
+        assert_eq!(dial.position(), 99);
+
+        let mut dial = Dial::new(100, 0);
+        assert_eq!(dial.rotate(5), 0);
+        assert_eq!(dial.position(), 5);
+    }
+
+    #[test]
+    fn landing_on_zero_counts() {
+        let mut dial = Dial::default();
+        assert_eq!(dial.rotate(-50), 1);
+        assert_eq!(dial.position(), 0);
+
+        let mut dial = Dial::default();
+        assert_eq!(dial.rotate(50), 1);
+        assert_eq!(dial.position(), 0);
+    }
+
+    #[test]
+    fn counts_every_lap() {
+        let mut dial = Dial::default();
+        assert_eq!(dial.rotate(1000), 10);
+        assert_eq!(dial.position(), 50);
+        assert_eq!(dial.rotate(-1000), 10);
+        assert_eq!(dial.position(), 50);
+
+        let mut dial = Dial::new(100, 0);
+        assert_eq!(dial.rotate(-200), 2);
+        assert_eq!(dial.position(), 0);
+        assert_eq!(dial.rotate(250), 2);
+        assert_eq!(dial.position(), 50);
+    }
+
+    #[test]
+    fn zero_amount_stays_put() {
+        let mut dial = Dial::new(100, 0);
+        assert_eq!(dial.rotate(0), 0);
+        assert_eq!(dial.position(), 0);
+
+        let mut dial = Dial::default();
+        assert_eq!(dial.rotate(0), 0);
+        assert_eq!(dial.position(), 50);
+    }
+
+    #[test]
+    fn other_sizes_and_starts() {
+        let mut dial = Dial::new(7, -2);
+        assert_eq!(dial.position(), 5);
+        assert_eq!(dial.rotate(2), 1);
+        assert_eq!(dial.position(), 0);
+        assert_eq!(dial.rotate(-15), 2);
+        assert_eq!(dial.position(), 6);
+        assert_eq!(dial.rotate(9), 2);
+        assert_eq!(dial.position(), 1);
+
+        let mut dial = Dial::new(1, 3);
+        assert_eq!(dial.position(), 0);
+        assert_eq!(dial.rotate(-4), 4);
+        assert_eq!(dial.rotate(4), 4);
+    }
+}
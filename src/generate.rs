@@ -0,0 +1,283 @@
+//! Random puzzle inputs of controlled size, in the format the parsers accept.
+//!
+//! Each generator takes a `size` that scales the input roughly linearly: the
+//! number of lines, ranges, problems or machines. They feed the cross-checks
+//! of `main check` and can be used for benchmarks.
+
+use std::collections::HashSet;
+
+use crate::rng::Rng;
+
+/// Random input of roughly the given size.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// Random number with 1 to `max_digits` digits, without leading zeros.
+fn number(rng: &mut Rng, max_digits: u32) -> i64 {
+    let digits = rng.range(1, max_digits as i64) as u32;
+    rng.range(10_i64.pow(digits - 1), 10_i64.pow(digits) - 1)
+}
+
+/// 2025 day 1: `size` rotations such as `L68` or `R1000`.
+pub fn rotations(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size.max(1) {
+        let direction = if rng.chance(0.5) { 'L' } else { 'R' };
+        // Mostly small turns, with the occasional turn of several laps
+        let amount = if rng.chance(0.8) {
+            rng.range(0, 120)
+        } else {
+            rng.range(0, 1000)
+        };
+        input.push_str(&format!("{}{}\n", direction, amount));
+    }
+    input
+}
+
+/// 2025 day 2: `size` comma-separated ranges of product ids, at most a
+/// thousand ids wide so brute force stays cheap.
+pub fn product_id_ranges(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let start = number(rng, 10);
+            let end = start + rng.range(0, 1000);
+            format!("{}-{}", start, end)
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// 2025 day 3: `size` banks of 12 to 20 digits from 1 to 9.
+pub fn battery_banks(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size.max(1) {
+        let len = rng.range(12, 20);
+        for _ in 0..len {
+            input.push_str(&rng.range(1, 9).to_string());
+        }
+        input.push('\n');
+    }
+    input
+}
+
+/// 2025 day 4: a `size` x `size` grid where about 60% of the cells hold a roll.
+pub fn paper_grid(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut input = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            input.push(if rng.chance(0.6) { '@' } else { '.' });
+        }
+        input.push('\n');
+    }
+    input
+}
+
+/// 2025 day 5: `size` overlapping fresh ranges, a blank line, then `size`
+/// ingredient ids around them.
+pub fn fresh_ranges(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let limit = 100 * size as i64 + 100;
+    let mut input = String::new();
+    for _ in 0..size {
+        let start = rng.range(1, limit);
+        let end = start + rng.range(0, limit / 10);
+        input.push_str(&format!("{}-{}\n", start, end));
+    }
+    input.push('\n');
+    for _ in 0..size {
+        input.push_str(&format!("{}\n", rng.range(1, limit + limit / 10)));
+    }
+    input
+}
+
+/// 2025 day 6: a worksheet of `size` problems with 2 to 4 operands of up to 4
+/// digits each. Operands are aligned left or right within a problem, and all
+/// lines are padded to the same width.
+pub fn worksheet(rng: &mut Rng, size: usize) -> String {
+    let rows = rng.range(2, 4) as usize;
+    let mut lines = vec![String::new(); rows + 1];
+
+    for problem in 0..size.max(1) {
+        if problem > 0 {
+            for line in lines.iter_mut() {
+                line.push(' ');
+            }
+        }
+
+        let numbers = (0..rows)
+            .map(|_| number(rng, 4).to_string())
+            .collect::<Vec<String>>();
+        let width = numbers.iter().map(String::len).max().unwrap();
+        let left = rng.chance(0.5);
+
+        for (line, number) in lines.iter_mut().zip(&numbers) {
+            if left {
+                line.push_str(&format!("{:<width$}", number));
+            } else {
+                line.push_str(&format!("{:>width$}", number));
+            }
+        }
+        let operator = rng.choose(&['+', '*']);
+        lines[rows].push_str(&format!("{:<width$}", operator));
+    }
+
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+/// 2025 day 7: a manifold with `size` splitter rows below the start, each
+/// followed by an empty row. Splitters sit in the cone the beam can reach.
+pub fn manifold(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let width = 2 * size + 1;
+    let center = size;
+
+    let mut rows = vec![];
+    let mut start = vec!['.'; width];
+    start[center] = 'S';
+    rows.push(start);
+    rows.push(vec!['.'; width]);
+
+    for level in 0..size {
+        let mut row = vec!['.'; width];
+        for col in (center - level..=center + level).step_by(2) {
+            if rng.chance(0.7) {
+                row[col] = '^';
+            }
+        }
+        rows.push(row);
+        rows.push(vec!['.'; width]);
+    }
+
+    rows.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+/// 2025 day 8: `size` distinct junction boxes with coordinates below 100000.
+pub fn point_cloud(rng: &mut Rng, size: usize) -> String {
+    let mut seen = HashSet::new();
+    let mut input = String::new();
+    while seen.len() < size.max(2) {
+        let point = (
+            rng.range(0, 99_999),
+            rng.range(0, 99_999),
+            rng.range(0, 99_999),
+        );
+        if seen.insert(point) {
+            input.push_str(&format!("{},{},{}\n", point.0, point.1, point.2));
+        }
+    }
+    input
+}
+
+/// 2025 day 9: a simple rectilinear loop of red tiles, shaped as a skyline of
+/// `size` columns on a flat base, listed in order around the loop.
+pub fn rectilinear_loop(rng: &mut Rng, size: usize) -> String {
+    let columns = size.max(1);
+
+    // Column edges from left to right, and heights differing between
+    // neighbours so every listed tile is a corner
+    let mut xs = vec![rng.range(0, 10)];
+    for _ in 0..columns {
+        let x = xs.last().unwrap() + rng.range(1, 20);
+        xs.push(x);
+    }
+    let mut heights: Vec<i64> = vec![];
+    for _ in 0..columns {
+        let mut height = rng.range(1, 40);
+        while heights.last() == Some(&height) {
+            height = rng.range(1, 40);
+        }
+        heights.push(height);
+    }
+
+    let mut points = vec![(xs[0], 0), (xs[columns], 0)];
+    for i in (0..columns).rev() {
+        points.push((xs[i + 1], heights[i]));
+        points.push((xs[i], heights[i]));
+    }
+
+    points
+        .iter()
+        .map(|(x, y)| format!("{},{}\n", x, y))
+        .collect()
+}
+
+/// 2025 day 10: `size` machines of 3 to 8 lights. The light diagram and the
+/// joltages come from random button presses, so every machine is solvable.
+pub fn machines(rng: &mut Rng, size: usize) -> String {
+    machines_of(rng, size, 8, 5, 0.0)
+}
+
+/// 2025 day 10: like [`machines`] with at most 5 lights and 3 presses of
+/// each button, small enough for a search without heuristic. Some buttons
+/// list a light twice, which toggles it twice and adds 2 to its joltage.
+pub fn small_machines(rng: &mut Rng, size: usize) -> String {
+    machines_of(rng, size, 5, 3, 0.1)
+}
+
+fn machines_of(
+    rng: &mut Rng,
+    size: usize,
+    max_lights: i64,
+    max_presses: i64,
+    repeat: f64,
+) -> String {
+    let mut input = String::new();
+
+    for _ in 0..size.max(1) {
+        let lights = rng.range(3, max_lights) as usize;
+        let mut buttons = (0..rng.range(2, lights as i64 + 2))
+            .map(|_| {
+                let mut button = vec![];
+                for light in 0..lights {
+                    if rng.chance(0.4) {
+                        button.push(light);
+                        if repeat > 0.0 && rng.chance(repeat) {
+                            button.push(light);
+                        }
+                    }
+                }
+                button
+            })
+            .filter(|button| !button.is_empty())
+            .collect::<Vec<Vec<usize>>>();
+        // Every light is wired to at least one button
+        for light in 0..lights {
+            if !buttons.iter().any(|button| button.contains(&light)) {
+                buttons.push(vec![light]);
+            }
+        }
+
+        let mut diagram = vec!['.'; lights];
+        let mut joltages = vec![0; lights];
+        for button in &buttons {
+            let toggle = rng.chance(0.5);
+            let presses = rng.range(0, max_presses);
+            for &light in button {
+                if toggle {
+                    diagram[light] = if diagram[light] == '.' { '#' } else { '.' };
+                }
+                joltages[light] += presses;
+            }
+        }
+
+        let buttons = buttons
+            .iter()
+            .map(|button| {
+                let lights = button.iter().map(usize::to_string).collect::<Vec<_>>();
+                format!("({})", lights.join(","))
+            })
+            .collect::<Vec<String>>();
+        let joltages = joltages.iter().map(i64::to_string).collect::<Vec<_>>();
+
+        input.push_str(&format!(
+            "[{}] {} {{{}}}\n",
+            diagram.iter().collect::<String>(),
+            buttons.join(" "),
+            joltages.join(",")
+        ));
+    }
+
+    input
+}
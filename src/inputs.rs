@@ -0,0 +1,204 @@
+//! Locating puzzle inputs, fetching and caching them when missing.
+//!
+//! Inputs are cached as `<assets>/<year>/day_<day>.input`. A missing input is
+//! retrieved from the configured [`Source`] and written to the cache, so each
+//! input is fetched at most once.
+
+use std::{
+    fmt, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use crate::normalise::{Normalisation, normalise};
+
+#[derive(Debug)]
+pub enum InputError {
+    Io(PathBuf, io::Error),
+    /// Not cached and no source is configured.
+    Missing(PathBuf),
+    Fetch(String),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            InputError::Missing(path) => write!(
+                f,
+                "{} not found and no input source configured (set AOC_INPUT_MIRROR or AOC_SESSION)",
+                path.display()
+            ),
+            InputError::Fetch(e) => write!(f, "Failed to fetch input: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Where missing inputs are retrieved from.
+pub trait Source {
+    fn fetch(&self, year: u32, day: u32) -> Result<String, InputError>;
+}
+
+fn input_file(dir: &Path, year: u32, day: u32) -> PathBuf {
+    dir.join(year.to_string())
+        .join(format!("day_{}.input", day))
+}
+
+/// A local directory laid out like the assets directory, for offline use.
+pub struct Mirror {
+    dir: PathBuf,
+}
+
+impl Mirror {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Mirror { dir: dir.into() }
+    }
+}
+
+impl Source for Mirror {
+    fn fetch(&self, year: u32, day: u32) -> Result<String, InputError> {
+        let path = input_file(&self.dir, year, day);
+        fs::read_to_string(&path).map_err(|e| InputError::Io(path, e))
+    }
+}
+
+/// An HTTP endpoint serving `<base_url>/<year>/day/<day>/input` to a session
+/// cookie. Requests go through `curl` so that HTTPS works without extra
+/// dependencies.
+pub struct Http {
+    base_url: String,
+    session: String,
+}
+
+impl Http {
+    pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Http {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+}
+
+impl Source for Http {
+    fn fetch(&self, year: u32, day: u32) -> Result<String, InputError> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        curl(&url, &self.session, &[]).map_err(InputError::Fetch)
+    }
+}
+
+/// The body served at `url` to the session cookie, with `args` passed on to
+/// `curl`, e.g. form fields.
+pub(crate) fn curl(url: &str, session: &str, args: &[&str]) -> Result<String, String> {
+    // The session is passed through a config on stdin to keep it out of the
+    // process list.
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--config", "-"])
+        .args(args)
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("cannot run curl: {}", e))?;
+
+    let config = format!(
+        "header = \"Cookie: session={}\"\nuser-agent = \"Advent-of-code solutions (local input cache)\"\n",
+        session
+    );
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(config.as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(format!(
+            "{}: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    String::from_utf8(output.stdout).map_err(|e| e.to_string())
+}
+
+pub struct Inputs {
+    assets: PathBuf,
+    source: Option<Box<dyn Source>>,
+}
+
+impl Inputs {
+    pub fn new(assets: impl Into<PathBuf>) -> Self {
+        Inputs {
+            assets: assets.into(),
+            source: None,
+        }
+    }
+
+    pub fn with_source(mut self, source: impl Source + 'static) -> Self {
+        self.source = Some(Box::new(source));
+        self
+    }
+
+    /// Cache under `assets/`, with the source taken from the environment:
+    /// `AOC_INPUT_MIRROR` names a mirror directory, otherwise `AOC_SESSION`
+    /// (and optionally `AOC_INPUT_URL`) configure the HTTP source.
+    pub fn from_env() -> Self {
+        let inputs = Inputs::new("assets");
+
+        if let Ok(dir) = std::env::var("AOC_INPUT_MIRROR") {
+            inputs.with_source(Mirror::new(dir))
+        } else if let Ok(session) = std::env::var("AOC_SESSION") {
+            let url =
+                std::env::var("AOC_INPUT_URL").unwrap_or_else(|_| Http::DEFAULT_URL.to_string());
+            inputs.with_source(Http::new(&url, &session))
+        } else {
+            inputs
+        }
+    }
+
+    /// Path of the cached input, whether or not it exists yet.
+    pub fn path(&self, year: u32, day: u32) -> PathBuf {
+        input_file(&self.assets, year, day)
+    }
+
+    /// Read the cached input, fetching and caching it first if missing.
+    pub fn load(&self, year: u32, day: u32) -> Result<String, InputError> {
+        let path = self.path(year, day);
+
+        if path.exists() {
+            return fs::read_to_string(&path).map_err(|e| InputError::Io(path, e));
+        }
+
+        let Some(source) = &self.source else {
+            return Err(InputError::Missing(path));
+        };
+
+        let contents = source.fetch(year, day)?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| InputError::Io(parent.to_path_buf(), e))?;
+        }
+        fs::write(&path, &contents).map_err(|e| InputError::Io(path, e))?;
+
+        Ok(contents)
+    }
+
+    /// The input as the parsers expect it: [`Inputs::load`] followed by
+    /// [`normalise`]. The cache keeps the input as fetched.
+    pub fn read(
+        &self,
+        year: u32,
+        day: u32,
+        normalisation: Normalisation,
+    ) -> Result<String, InputError> {
+        Ok(normalise(&self.load(year, day)?, normalisation))
+    }
+}
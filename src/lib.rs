@@ -0,0 +1,23 @@
+pub mod accessibility;
+pub mod battery;
+pub mod bigint;
+pub mod check;
+pub mod dial;
+pub mod generate;
+pub mod inputs;
+pub mod memo;
+pub mod normalise;
+pub mod parallel;
+pub mod parser;
+pub mod progress;
+pub mod registry;
+pub mod rng;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub mod submit;
+pub mod union_find;
+pub mod worksheet;
+
+#[path = "2025/mod.rs"]
+pub mod y2025;
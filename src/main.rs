@@ -1,3 +1,492 @@
-fn main() {
-    println!("Hello, world!");
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use aoc::{
+    check::Failure,
+    inputs::Inputs,
+    parallel, registry,
+    rng::Rng,
+    scaffold,
+    solution::{Answer, Check, Error, Solution},
+    submit::{self, Http, Ledger, Submission},
+};
+
+const USAGE: &str = "Usage:
+    main run [year] [day] [options]   Run one day, every day of a year, or everything
+        --debug                       Print day-specific diagnostics first
+        --trace [path]                Write the day's trace (day 1: a CSV of the dial) to
+                                      the file, or alone to stdout
+        --example [name]              Run the inline examples instead of the input
+        --part <1|2>                  Run only one part
+        --jobs <n>                    Threads for days and solvers (default: one per core)
+    main check [year] [day] [options] Compare optimised solvers with reference ones
+        --cases <n>                   Random inputs per check (default 200)
+        --seed <s>                    Seed of the first check (default: from the clock)
+    main generate <year> <day> [options]
+                                      Print a random input for stress tests
+        --size <n>                    Lines, ranges or problems (default 100)
+        --seed <s>                    Seed of the input (default: from the clock)
+    main submit <year> <day> <part> [answer]
+                                      Submit an answer, the solver's if not given,
+                                      unless the answer ledger already rules it out
+    main list                         List the implemented years, days and parts
+    main new <year> <day>             Create the solution files of a new day
+
+Environment:
+    AOC_INPUT_MIRROR                  Directory of <year>/day_<day>.input files to read
+                                      inputs from
+    AOC_SESSION                       Session cookie to download inputs and submit answers
+    AOC_INPUT_URL, AOC_SUBMIT_URL     Site to download from and submit to
+    AOC_MACHINE_SECONDS               Time limit of each 2025 day 10 machine (default 60,
+                                      0 for none)
+    AOC_MACHINE_STATES                States limit of each 2025 day 10 machine (default
+                                      2000000, 0 for none)";
+
+fn parse_number(arg: Option<&String>, name: &str) -> Result<u32, Error> {
+    let arg = arg.ok_or_else(|| format!("Missing <{}>\n{}", name, USAGE))?;
+    arg.parse()
+        .map_err(|_| format!("Invalid {}: {}", name, arg).into())
+}
+
+#[derive(Default)]
+struct RunOptions {
+    debug: bool,
+    /// `Some(None)` runs every example, `Some(Some(name))` only the named one.
+    example: Option<Option<String>>,
+    /// Only this part, rather than both.
+    part: Option<u8>,
+    /// `Some(None)` writes the trace to stdout, `Some(Some(path))` to a file.
+    trace: Option<Option<PathBuf>>,
+}
+
+impl RunOptions {
+    fn parts(&self) -> &'static [u8] {
+        match self.part {
+            Some(1) => &[1],
+            Some(2) => &[2],
+            _ => &[1, 2],
+        }
+    }
+}
+
+fn print_part(out: &mut dyn Write, part: u8, answer: Result<Answer, Error>) -> io::Result<()> {
+    match answer {
+        Ok(answer) => writeln!(out, "Part {}: {}", part, answer),
+        Err(e) => writeln!(out, "Part {}: error: {}", part, e),
+    }
+}
+
+fn run_examples(
+    out: &mut dyn Write,
+    year: u32,
+    day: u32,
+    solution: &dyn Solution,
+    name: Option<&str>,
+    parts: &[u8],
+) -> Result<(), Error> {
+    let examples = solution
+        .examples()
+        .iter()
+        .filter(|example| name.is_none_or(|name| name == example.name))
+        .collect::<Vec<_>>();
+    if examples.is_empty() {
+        return Err(format!(
+            "No example {}for {} day {}",
+            name.map_or(String::new(), |n| format!("'{}' ", n)),
+            year,
+            day
+        )
+        .into());
+    }
+
+    for example in examples {
+        writeln!(out, "{} day {} ({})", year, day, example.name)?;
+
+        for &part in parts {
+            match example.check(solution, part) {
+                Check::Passed(answer) => writeln!(out, "Part {}: {} (ok)", part, answer)?,
+                Check::Failed {
+                    expected,
+                    actual: Ok(answer),
+                } => writeln!(out, "Part {}: {} (expected {})", part, answer, expected)?,
+                Check::Failed {
+                    expected,
+                    actual: Err(e),
+                } => writeln!(out, "Part {}: error: {} (expected {})", part, e, expected)?,
+                Check::Skipped => print_part(
+                    out,
+                    part,
+                    example
+                        .solution
+                        .unwrap_or(solution)
+                        .solve(part, example.input),
+                )?,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn run_day(
+    out: &mut dyn Write,
+    year: u32,
+    day: u32,
+    solution: &dyn Solution,
+    options: &RunOptions,
+) -> Result<(), Error> {
+    if let Some(name) = &options.example {
+        return run_examples(out, year, day, solution, name.as_deref(), options.parts());
+    }
+
+    writeln!(out, "{} day {}", year, day)?;
+
+    let input = Inputs::from_env().read(year, day, solution.normalisation())?;
+
+    if options.debug {
+        solution.debug(&input, out)?;
+    }
+
+    // Work both parts need is done once
+    match solution.prepare(&input) {
+        Ok(prepared) => {
+            for &part in options.parts() {
+                print_part(out, part, prepared.solve(part))?;
+            }
+        }
+        Err(e) => {
+            let e = e.to_string();
+            for &part in options.parts() {
+                print_part(out, part, Err(e.as_str().into()))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Run the days concurrently, printing the output of each as soon as it and
+/// every earlier day are done. The output stops after the first day that
+/// fails. With several days, each solves on a single thread, see
+/// [`parallel`].
+fn run_days(days: &[(u32, u32, &dyn Solution)], options: &RunOptions) -> Result<(), Error> {
+    parallel::for_each(
+        days,
+        |&(year, day, solution)| {
+            let mut out = vec![];
+            let result = run_day(&mut out, year, day, solution, options);
+            (out, result)
+        },
+        |(out, result)| {
+            let mut stdout = io::stdout().lock();
+            stdout.write_all(&out)?;
+            stdout.flush()?;
+            result
+        },
+    )
+}
+
+/// Write the trace of one day to `path`, or to stdout instead of the answers.
+fn trace_day(
+    (year, day, solution): (u32, u32, &dyn Solution),
+    path: Option<&Path>,
+) -> Result<(), Error> {
+    let tracer = solution
+        .tracer()
+        .ok_or_else(|| format!("No trace for {} day {}", year, day))?;
+    let input = Inputs::from_env().read(year, day, solution.normalisation())?;
+
+    match path {
+        Some(path) => {
+            let mut file = io::BufWriter::new(
+                fs::File::create(path).map_err(|e| format!("{}: {}", path.display(), e))?,
+            );
+            tracer(&input, &mut file)?;
+            file.flush()?;
+        }
+        None => tracer(&input, &mut io::stdout().lock())?,
+    }
+
+    Ok(())
+}
+
+fn run(args: &[String]) -> Result<(), Error> {
+    let mut positional = vec![];
+    let mut options = RunOptions::default();
+
+    let mut args = args.iter().peekable();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--debug" => options.debug = true,
+            "--jobs" => parallel::set_jobs(parse_number(args.next(), "n")? as usize),
+            "--part" => match parse_number(args.next(), "part")? {
+                part @ (1 | 2) => options.part = Some(part as u8),
+                part => return Err(format!("Invalid part: {}", part).into()),
+            },
+            "--trace" => {
+                // Like example names, a path that is a number would be a day
+                let path = args
+                    .next_if(|next| !next.starts_with("--") && next.parse::<u32>().is_err())
+                    .map(PathBuf::from);
+                options.trace = Some(path);
+            }
+            "--example" => {
+                // Example names are never numbers, so `--example 2025 1` still
+                // reads the year and day
+                let name = args
+                    .next_if(|next| !next.starts_with("--") && next.parse::<u32>().is_err())
+                    .cloned();
+                options.example = Some(name);
+            }
+            flag if flag.starts_with("--") => {
+                return Err(format!("Unknown option: {}\n{}", flag, USAGE).into());
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    if options.trace.is_some() && options.example.is_some() {
+        return Err("--trace runs on the input, not the examples".into());
+    }
+
+    if positional.is_empty() {
+        if options.trace.is_some() {
+            return Err("--trace needs a year and a day".into());
+        }
+        return run_days(&registry::all().collect::<Vec<_>>(), &options);
+    }
+
+    let year = parse_number(positional.first().copied(), "year")?;
+    let solutions = registry::year(year).ok_or_else(|| format!("No solutions for {}", year))?;
+
+    let days = match positional.get(1) {
+        Some(_) => {
+            let day = parse_number(positional.get(1).copied(), "day")?;
+            let solution = registry::solution(year, day)
+                .ok_or_else(|| format!("No solution for {} day {}", year, day))?;
+            vec![(year, day, solution)]
+        }
+        None if options.trace.is_some() => return Err("--trace needs a day".into()),
+        None => solutions
+            .iter()
+            .map(|(day, solution)| (year, *day, *solution))
+            .collect(),
+    };
+
+    match &options.trace {
+        // Only the trace goes to stdout, so it can be piped
+        Some(None) => trace_day(days[0], None),
+        Some(Some(path)) => {
+            trace_day(days[0], Some(path))?;
+            run_days(&days, &options)
+        }
+        None => run_days(&days, &options),
+    }
+}
+
+fn parse_seed(arg: Option<&String>) -> Result<u64, Error> {
+    let arg = arg.ok_or_else(|| format!("Missing <s>\n{}", USAGE))?;
+    arg.parse()
+        .map_err(|_| format!("Invalid seed: {}", arg).into())
+}
+
+fn clock_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
+fn check(args: &[String]) -> Result<(), Error> {
+    let mut positional = vec![];
+    let mut cases = 200;
+    let mut seed = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--cases" => cases = parse_number(args.next(), "n")? as usize,
+            "--seed" => seed = Some(parse_seed(args.next())?),
+            flag if flag.starts_with("--") => {
+                return Err(format!("Unknown option: {}\n{}", flag, USAGE).into());
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let year = positional
+        .first()
+        .map(|arg| parse_number(Some(arg), "year"))
+        .transpose()?;
+    let day = positional
+        .get(1)
+        .map(|arg| parse_number(Some(arg), "day"))
+        .transpose()?;
+    let seed = seed.unwrap_or_else(clock_seed);
+
+    let mut failures = 0;
+    for (y, d, solution) in registry::all() {
+        if year.is_some_and(|year| year != y) || day.is_some_and(|day| day != d) {
+            continue;
+        }
+
+        for cross_check in solution.cross_checks() {
+            match cross_check.run(cases, seed) {
+                Ok(compared) => println!(
+                    "{} day {} {}: ok ({} of {} inputs compared)",
+                    y, d, cross_check.name, compared, cases
+                ),
+                Err(Failure::NothingCompared) => {
+                    failures += 1;
+                    println!(
+                        "{} day {} {}: the reference rejected every input (seed {})",
+                        y, d, cross_check.name, seed
+                    );
+                }
+                Err(Failure::Mismatch(mismatch)) => {
+                    failures += 1;
+                    println!(
+                        "{} day {} {}: mismatch (seed {})",
+                        y, d, cross_check.name, mismatch.seed
+                    );
+                    println!("Input:\n{}", mismatch.input);
+                    println!("Reference: {:?}", mismatch.reference);
+                    println!("Optimised: {:?}", mismatch.optimised);
+                }
+            }
+        }
+    }
+
+    match failures {
+        0 => Ok(()),
+        n => Err(format!("{} cross-check(s) failed", n).into()),
+    }
+}
+
+fn generate(args: &[String]) -> Result<(), Error> {
+    let mut positional = vec![];
+    let mut size = 100;
+    let mut seed = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => size = parse_number(args.next(), "n")? as usize,
+            "--seed" => seed = Some(parse_seed(args.next())?),
+            flag if flag.starts_with("--") => {
+                return Err(format!("Unknown option: {}\n{}", flag, USAGE).into());
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let year = parse_number(positional.first().copied(), "year")?;
+    let day = parse_number(positional.get(1).copied(), "day")?;
+    let solution = registry::solution(year, day)
+        .ok_or_else(|| format!("No solution for {} day {}", year, day))?;
+    let generator = solution
+        .generator()
+        .ok_or_else(|| format!("No generator for {} day {}", year, day))?;
+
+    let mut rng = Rng::new(seed.unwrap_or_else(clock_seed));
+    print!("{}", generator(&mut rng, size));
+
+    Ok(())
+}
+
+fn submit(args: &[String]) -> Result<(), Error> {
+    let year = parse_number(args.first(), "year")?;
+    let day = parse_number(args.get(1), "day")?;
+    let part = match parse_number(args.get(2), "part")? {
+        part @ (1 | 2) => part as u8,
+        part => return Err(format!("Invalid part: {}", part).into()),
+    };
+
+    let answer = match args.get(3) {
+        Some(answer) => answer.trim().to_string(),
+        None => {
+            let solution = registry::solution(year, day)
+                .ok_or_else(|| format!("No solution for {} day {}", year, day))?;
+            let input = Inputs::from_env().read(year, day, solution.normalisation())?;
+            solution.solve(part, &input)?.to_string()
+        }
+    };
+
+    println!("{} day {} part {}: {}", year, day, part, answer);
+
+    // The ledger may settle it without a session
+    let mut ledger = Ledger::open(Ledger::DEFAULT_PATH)?;
+    if let Err(refusal) = ledger.check(year, day, part, &answer) {
+        return Err(format!("Not submitted: {}", refusal).into());
+    }
+    let transport = Http::from_env()?;
+
+    match submit::submit(&mut ledger, &transport, year, day, part, &answer)? {
+        Submission::Judged(verdict) => {
+            println!("Verdict: {}", verdict);
+            Ok(())
+        }
+        Submission::Refused(refusal) => Err(format!("Not submitted: {}", refusal).into()),
+    }
+}
+
+fn list() -> Result<(), Error> {
+    for year in registry::years() {
+        println!("{}", year);
+
+        for (day, solution) in registry::year(year).unwrap_or_default() {
+            let parts = solution
+                .implemented()
+                .iter()
+                .enumerate()
+                .filter(|(_, implemented)| **implemented)
+                .map(|(i, _)| (i + 1).to_string())
+                .collect::<Vec<String>>();
+
+            if parts.is_empty() {
+                println!("  day {:>2}: not implemented", day);
+            } else {
+                println!("  day {:>2}: part {}", day, parts.join(", "));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn new(args: &[String]) -> Result<(), Error> {
+    let year = parse_number(args.first(), "year")?;
+    let day = parse_number(args.get(1), "day")?;
+
+    for path in scaffold::new_day(Path::new("."), year, day)? {
+        println!("Wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+
+    let result = match args.first().map(String::as_str) {
+        Some("run") => run(&args[1..]),
+        Some("check") => check(&args[1..]),
+        Some("generate") => generate(&args[1..]),
+        Some("submit") => submit(&args[1..]),
+        Some("list") => list(),
+        Some("new") => new(&args[1..]),
+        _ => Err(USAGE.into()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
 }
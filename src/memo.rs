@@ -0,0 +1,203 @@
+//! Memoised recursion without the call stack.
+//!
+//! A recursive solver is written as a closure from a key to its value, which
+//! looks up the values of its sub-problems through [`Sub::get`]. A lookup
+//! that misses records the sub-problem and the closure gives up with `None`,
+//! usually through `?`. [`Memo::solve`] then solves the missing sub-problems
+//! from an explicit stack and calls the closure again, so deep recursions
+//! cannot overflow the stack and every key is computed once.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::Hash,
+};
+
+#[derive(Debug)]
+pub enum MemoError<K> {
+    /// The key needs itself, directly or through other keys.
+    Cycle(K),
+    /// The closure gave up on the key without missing any sub-problem.
+    Stuck(K),
+}
+
+impl<K: fmt::Debug> fmt::Display for MemoError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MemoError::Cycle(key) => write!(f, "{:?} depends on itself", key),
+            MemoError::Stuck(key) => write!(f, "{:?} cannot be solved", key),
+        }
+    }
+}
+
+impl<K: fmt::Debug> std::error::Error for MemoError<K> {}
+
+/// The values solved so far, as seen by the closure.
+pub struct Sub<'a, K, V> {
+    cache: &'a HashMap<K, V>,
+    missing: Vec<K>,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Sub<'_, K, V> {
+    /// The value of a sub-problem, or `None` after recording it as missing.
+    /// Look up every sub-problem before using `?` on them, so all the
+    /// missing ones are solved before the next call.
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let value = self.cache.get(key).cloned();
+        if value.is_none() {
+            self.missing.push(key.clone());
+        }
+        value
+    }
+}
+
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo {
+            cache: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The value of a key solved earlier.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// Number of keys solved.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// The value of `key`, solving it and its sub-problems with `solve`.
+    /// Values stay cached for later calls.
+    pub fn solve<F>(&mut self, key: K, mut solve: F) -> Result<V, MemoError<K>>
+    where
+        F: FnMut(&K, &mut Sub<K, V>) -> Option<V>,
+    {
+        // Keys to solve, the last one first. Everything above a waiting key
+        // was pushed for it, so a waiting key needed again is a cycle.
+        let mut stack = vec![key.clone()];
+        let mut waiting = HashSet::new();
+
+        while let Some(top) = stack.last() {
+            if self.cache.contains_key(top) {
+                stack.pop();
+                continue;
+            }
+
+            let mut sub = Sub {
+                cache: &self.cache,
+                missing: vec![],
+            };
+            match solve(top, &mut sub) {
+                Some(value) => {
+                    let top = stack.pop().unwrap();
+                    waiting.remove(&top);
+                    self.cache.insert(top, value);
+                }
+                None if sub.missing.is_empty() => return Err(MemoError::Stuck(top.clone())),
+                None => {
+                    let missing = sub.missing;
+                    if let Some(cycle) = missing.iter().find(|key| waiting.contains(*key)) {
+                        return Err(MemoError::Cycle(cycle.clone()));
+                    }
+                    waiting.insert(top.clone());
+                    stack.extend(missing);
+                }
+            }
+        }
+
+        Ok(self.cache[&key].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_a_key_that_needs_itself() {
+        let mut memo = Memo::<u32, u32>::new();
+        let result = memo.solve(1, |&n, sub| Some(sub.get(&n)? + 1));
+        assert!(matches!(result, Err(MemoError::Cycle(1))));
+    }
+
+    #[test]
+    fn finds_a_cycle_through_other_keys() {
+        // 1 needs 2, 2 needs 3 and 3 needs 1 again; 4 is solved on the way
+        let mut memo = Memo::<u32, u32>::new();
+        let result = memo.solve(1, |&n, sub| match n {
+            4 => Some(0),
+            3 => Some(sub.get(&4)? + sub.get(&1)?),
+            n => Some(sub.get(&(n + 1))?),
+        });
+        assert!(matches!(result, Err(MemoError::Cycle(1))));
+        assert_eq!(memo.get(&4), Some(&0));
+    }
+
+    #[test]
+    fn solves_shared_sub_problems_once() {
+        let mut memo = Memo::<u64, u64>::new();
+        let mut solved = HashMap::<u64, usize>::new();
+
+        let fibonacci = memo.solve(90, |&n, sub| {
+            let value = match n {
+                0 | 1 => n,
+                n => {
+                    let (a, b) = (sub.get(&(n - 1)), sub.get(&(n - 2)));
+                    a? + b?
+                }
+            };
+            *solved.entry(n).or_default() += 1;
+            Some(value)
+        });
+
+        assert_eq!(fibonacci.unwrap(), 2_880_067_194_370_816_120);
+        assert_eq!(memo.len(), 91);
+        assert!(solved.values().all(|&times| times == 1));
+
+        // Later calls start from the cache
+        let mut calls = 0;
+        let value = memo.solve(91, |&n, sub| {
+            calls += 1;
+            Some(sub.get(&(n - 1))? + sub.get(&(n - 2))?)
+        });
+        assert_eq!(value.unwrap(), 4_660_046_610_375_530_309);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn goes_deeper_than_the_call_stack() {
+        let mut memo = Memo::<u32, u64>::new();
+        let depth = memo.solve(100_000, |&n, sub| match n {
+            0 => Some(0),
+            n => Some(sub.get(&(n - 1))? + 1),
+        });
+        assert_eq!(depth.unwrap(), 100_000);
+        assert_eq!(memo.len(), 100_001);
+    }
+
+    #[test]
+    fn reports_a_key_given_up_on() {
+        let mut memo = Memo::<u32, u32>::new();
+        let result = memo.solve(3, |&n, sub| match n {
+            0 => None,
+            n => sub.get(&(n - 1)),
+        });
+        assert!(matches!(result, Err(MemoError::Stuck(0))));
+    }
+}
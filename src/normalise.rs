@@ -0,0 +1,104 @@
+//! Cleaning up puzzle inputs before parsing.
+//!
+//! Inputs saved by a browser or an editor may start with a byte order mark,
+//! end lines with `\r\n`, carry trailing spaces or end with blank lines. The
+//! parsers expect none of that, so inputs are normalised once when loaded.
+//! Days whose columns matter keep their spaces with
+//! [`Normalisation::LineEndings`], and [`Normalisation::Raw`] opts out.
+
+/// How a day's input is cleaned up, see [`crate::solution::Solution::normalisation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Normalisation {
+    /// Also trim the end of every line and drop trailing blank lines.
+    Full,
+    /// Only remove the byte order mark and turn `\r\n` into `\n`, keeping
+    /// every space for column-sensitive inputs.
+    LineEndings,
+    /// Leave the input untouched.
+    Raw,
+}
+
+const BOM: char = '\u{feff}';
+
+/// `input` cleaned up as `normalisation` says. Unless raw, a non-empty
+/// result ends with `\n`, and with exactly one when full.
+pub fn normalise(input: &str, normalisation: Normalisation) -> String {
+    if normalisation == Normalisation::Raw {
+        return input.to_string();
+    }
+
+    let input = input.strip_prefix(BOM).unwrap_or(input);
+    let mut lines = input
+        .split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .map(|line| match normalisation {
+            Normalisation::Full => line.trim_end(),
+            _ => line,
+        })
+        .collect::<Vec<&str>>();
+
+    // The text after the last `\n` is not a line if empty
+    if lines.last() == Some(&"") {
+        lines.pop();
+    }
+    if normalisation == Normalisation::Full {
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+    }
+
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use Normalisation::{Full, LineEndings, Raw};
+
+    #[test]
+    fn drops_the_byte_order_mark() {
+        assert_eq!(normalise("\u{feff}1\n2\n", Full), "1\n2\n");
+        assert_eq!(normalise("\u{feff}1 \n", LineEndings), "1 \n");
+        // Only at the start
+        assert_eq!(normalise("1\u{feff}\n", Full), "1\u{feff}\n");
+    }
+
+    #[test]
+    fn turns_crlf_into_lf() {
+        assert_eq!(normalise("a\r\nb\r\n", Full), "a\nb\n");
+        assert_eq!(normalise("a\r\nb", LineEndings), "a\nb\n");
+        // A lone `\r` is not a line ending
+        assert_eq!(normalise("a\rb\n", LineEndings), "a\rb\n");
+    }
+
+    #[test]
+    fn line_endings_keeps_spaces() {
+        let worksheet = "123 4  \r\n 5  67\r\n*   + \r\n";
+        assert_eq!(
+            normalise(worksheet, LineEndings),
+            "123 4  \n 5  67\n*   + \n"
+        );
+        assert_eq!(normalise(worksheet, Full), "123 4\n 5  67\n*   +\n");
+    }
+
+    #[test]
+    fn full_drops_trailing_blank_lines() {
+        assert_eq!(normalise("1\n2\n\n\n", Full), "1\n2\n");
+        assert_eq!(normalise("1\n2\n \r\n\t\n", Full), "1\n2\n");
+        assert_eq!(normalise("1\n2", Full), "1\n2\n");
+        // Blank lines between sections stay
+        assert_eq!(normalise("1\n\n2\n\n", Full), "1\n\n2\n");
+        assert_eq!(normalise("\n\n", Full), "");
+        assert_eq!(normalise("", Full), "");
+
+        assert_eq!(normalise("1\n  \n\n", LineEndings), "1\n  \n\n");
+    }
+
+    #[test]
+    fn raw_leaves_everything() {
+        let input = "\u{feff}a \r\n\r\nb\n\n";
+        assert_eq!(normalise(input, Raw), input);
+        assert_eq!(normalise("", Raw), "");
+    }
+}
@@ -0,0 +1,201 @@
+//! A parallel map over slices on scoped std threads.
+//!
+//! Items are handed out one at a time from a shared counter, so uneven work
+//! (one slow machine among fast ones) still keeps every thread busy. Results
+//! come back in the order of the items, whatever order they finish in.
+//!
+//! The number of threads is a process-wide setting, changed with `--jobs`.
+//! A map started from inside another map runs sequentially, so running days
+//! concurrently does not multiply the threads of the days themselves. The
+//! price is that a slow day among several, like 2025 day 10 under `run 2025`,
+//! solves its machines on one thread even once the other days are done; run
+//! it alone to give its machines every thread.
+
+use std::{
+    cell::Cell,
+    convert::Infallible,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+/// 0 until set, meaning one thread per available core.
+static JOBS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static IN_WORKER: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Use at most `jobs` threads per map; 1 disables parallelism.
+pub fn set_jobs(jobs: usize) {
+    JOBS.store(jobs.max(1), Ordering::Relaxed);
+}
+
+/// Number of threads a map uses.
+pub fn jobs() -> usize {
+    match JOBS.load(Ordering::Relaxed) {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        jobs => jobs,
+    }
+}
+
+/// `items.iter().map(f).collect()`, computed on up to [`jobs`] threads.
+pub fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let mut results = Vec::with_capacity(items.len());
+    let Ok(()) = for_each(items, f, |result| {
+        results.push(result);
+        Ok::<(), Infallible>(())
+    });
+    results
+}
+
+/// Map `items` with `f` on up to [`jobs`] threads, handing each result to
+/// `emit` on the calling thread, in the order of the items, as soon as it and
+/// every earlier one are done.
+///
+/// The first error of `emit` is returned; items not started by then are
+/// skipped.
+pub fn for_each<T, R, E, F>(
+    items: &[T],
+    f: F,
+    mut emit: impl FnMut(R) -> Result<(), E>,
+) -> Result<(), E>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = jobs().min(items.len());
+    if threads <= 1 || IN_WORKER.get() {
+        return items.iter().try_for_each(|item| emit(f(item)));
+    }
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            let sender = sender.clone();
+            let (next, f) = (&next, &f);
+            scope.spawn(move || {
+                IN_WORKER.set(true);
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(i) else {
+                        break;
+                    };
+                    if sender.send((i, f(item))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        // Results that came early wait here for the ones before them
+        let mut pending = (0..items.len()).map(|_| None).collect::<Vec<Option<R>>>();
+        let mut emitted = 0;
+        for (i, result) in receiver {
+            pending[i] = Some(result);
+            while let Some(result) = pending.get_mut(emitted).and_then(Option::take) {
+                emitted += 1;
+                if let Err(e) = emit(result) {
+                    // Stop handing out items; the ones running still finish
+                    next.store(items.len(), Ordering::Relaxed);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::Mutex, time::Duration};
+
+    #[test]
+    fn results_keep_the_order_of_the_items() {
+        set_jobs(4);
+        // Later items sleep less, so they tend to finish first
+        let items = (0..16).collect::<Vec<u64>>();
+        let results = map(&items, |&i| {
+            thread::sleep(Duration::from_millis(2 * (16 - i)));
+            i * i
+        });
+        assert_eq!(results, items.iter().map(|i| i * i).collect::<Vec<u64>>());
+
+        let mut emitted = vec![];
+        let Ok(()) = for_each(
+            &items,
+            |&i| {
+                thread::sleep(Duration::from_millis(i % 3));
+                i
+            },
+            |i| {
+                emitted.push(i);
+                Ok::<(), Infallible>(())
+            },
+        );
+        assert_eq!(emitted, items);
+    }
+
+    #[test]
+    fn an_error_from_emit_stops_the_map() {
+        set_jobs(4);
+        let items = (0..200).collect::<Vec<usize>>();
+        let started = AtomicUsize::new(0);
+        let mut emitted = vec![];
+
+        let result = for_each(
+            &items,
+            |&i| {
+                started.fetch_add(1, Ordering::Relaxed);
+                thread::sleep(Duration::from_millis(1));
+                i
+            },
+            |i| {
+                emitted.push(i);
+                if i == 2 { Err("stop") } else { Ok(()) }
+            },
+        );
+
+        assert_eq!(result, Err("stop"));
+        assert_eq!(emitted, [0, 1, 2]);
+        let started = started.load(Ordering::Relaxed);
+        assert!(started < items.len(), "all {} items started", started);
+    }
+
+    #[test]
+    fn a_map_inside_a_map_runs_on_the_worker_thread() {
+        set_jobs(4);
+        let outer = (0..4).collect::<Vec<usize>>();
+        let threads = Mutex::new(vec![]);
+
+        let sums = map(&outer, |&i| {
+            let worker = thread::current().id();
+            let inner = (0..8).collect::<Vec<usize>>();
+            let results = map(&inner, |&j| {
+                threads
+                    .lock()
+                    .unwrap()
+                    .push(thread::current().id() == worker);
+                i * 10 + j
+            });
+            results.iter().sum::<usize>()
+        });
+
+        assert_eq!(sums, [28, 108, 188, 268]);
+        let threads = threads.into_inner().unwrap();
+        assert_eq!(threads.len(), 32);
+        assert!(threads.iter().all(|&same| same));
+    }
+}
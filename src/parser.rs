@@ -0,0 +1,613 @@
+//! Parser combinators for puzzle inputs.
+//!
+//! A parser is any function that reads a value at a [`Cursor`] and moves the
+//! cursor past it. The functions here build parsers out of smaller ones, so a
+//! day describes its input format rather than splitting strings by hand: a
+//! line like `L68` is `pair(one_of("LR"), uint())`, and a whole input of them
+//! is `lines(...)` run by [`complete`]. Errors point at the line and column
+//! where the input stopped matching.
+//!
+//! Like Parsec, a parser that fails without reading anything lets the
+//! combinators around it try something else, while one that fails part way
+//! through is reported as is.
+
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the error, from 1.
+    pub line: usize,
+    /// Column of the error in characters, from 1.
+    pub column: usize,
+    pub message: String,
+    offset: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A position in the input being parsed.
+#[derive(Debug, Clone, Copy)]
+pub struct Cursor<'a> {
+    source: &'a str,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Cursor { source, offset: 0 }
+    }
+
+    /// The input not read yet.
+    pub fn rest(&self) -> &'a str {
+        &self.source[self.offset..]
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.offset == self.source.len()
+    }
+
+    /// Whether the cursor is at the end of a line or of the input.
+    pub fn is_at_line_end(&self) -> bool {
+        self.rest().is_empty() || self.rest().starts_with('\n')
+    }
+
+    fn advance(&mut self, len: usize) {
+        self.offset += len;
+    }
+
+    /// An error at the cursor.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        self.error_at(self.offset, message)
+    }
+
+    fn error_at(&self, offset: usize, message: impl Into<String>) -> ParseError {
+        let before = &self.source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: message.into(),
+            offset,
+        }
+    }
+
+    /// An error saying what was expected at the cursor and what is there.
+    pub fn expected(&self, what: &str) -> ParseError {
+        self.error(format!("expected {}, found {}", what, self.found()))
+    }
+
+    fn found(&self) -> String {
+        let rest = self.rest();
+        if rest.is_empty() {
+            return "end of input".into();
+        }
+        if rest.starts_with('\n') {
+            return "end of line".into();
+        }
+        let token = rest
+            .split(char::is_whitespace)
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .take(20)
+            .collect::<String>();
+        if token.is_empty() {
+            format!("{:?}", rest.chars().next().unwrap())
+        } else {
+            format!("'{}'", token)
+        }
+    }
+}
+
+pub trait Parser<T>: Fn(&mut Cursor<'_>) -> Result<T, ParseError> {}
+
+impl<T, F> Parser<T> for F where F: Fn(&mut Cursor<'_>) -> Result<T, ParseError> {}
+
+/// Run `parser` on the whole of `input`, allowing trailing newlines.
+pub fn complete<T>(parser: impl Parser<T>, input: &str) -> Result<T, ParseError> {
+    let mut cursor = Cursor::new(input);
+    let value = parser(&mut cursor)?;
+    let newlines = cursor.rest().len() - cursor.rest().trim_start_matches('\n').len();
+    cursor.advance(newlines);
+    if !cursor.is_at_end() {
+        return Err(cursor.expected("end of input"));
+    }
+    Ok(value)
+}
+
+/// Whether `error` happened before anything was read from `start`.
+fn failed_at(error: &ParseError, start: &Cursor) -> bool {
+    error.offset == start.offset
+}
+
+/// Exactly the text `expected`.
+pub fn tag(expected: &'static str) -> impl Parser<&'static str> {
+    move |cursor: &mut Cursor| {
+        if cursor.rest().starts_with(expected) {
+            cursor.advance(expected.len());
+            Ok(expected)
+        } else {
+            Err(cursor.expected(&format!("{:?}", expected)))
+        }
+    }
+}
+
+/// One of the characters in `chars`.
+pub fn one_of(chars: &'static str) -> impl Parser<char> {
+    move |cursor: &mut Cursor| match cursor.rest().chars().next() {
+        Some(c) if chars.contains(c) => {
+            cursor.advance(c.len_utf8());
+            Ok(c)
+        }
+        _ => {
+            let choices = chars
+                .chars()
+                .map(|c| format!("'{}'", c))
+                .collect::<Vec<String>>();
+            Err(cursor.expected(&format!("one of {}", choices.join(", "))))
+        }
+    }
+}
+
+/// Any amount of whitespace, newlines included.
+pub fn whitespace() -> impl Parser<()> {
+    |cursor: &mut Cursor| {
+        let rest = cursor.rest();
+        cursor.advance(rest.len() - rest.trim_start().len());
+        Ok(())
+    }
+}
+
+/// The number in the next `len` bytes, which are ASCII.
+fn number<T>(cursor: &mut Cursor, len: usize) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let text = &cursor.rest()[..len];
+    let value = text
+        .parse()
+        .map_err(|e| cursor.error(format!("invalid number '{}': {}", text, e)))?;
+    cursor.advance(len);
+    Ok(value)
+}
+
+fn digits(text: &str) -> usize {
+    text.bytes().take_while(u8::is_ascii_digit).count()
+}
+
+/// A decimal number without a sign.
+pub fn uint<T>() -> impl Parser<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    |cursor: &mut Cursor| match digits(cursor.rest()) {
+        0 => Err(cursor.expected("an unsigned integer")),
+        len => number(cursor, len),
+    }
+}
+
+/// A decimal number with an optional `-` or `+` sign.
+pub fn int<T>() -> impl Parser<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    |cursor: &mut Cursor| {
+        let rest = cursor.rest();
+        let sign = usize::from(rest.starts_with(['-', '+']));
+        match digits(&rest[sign..]) {
+            0 => Err(cursor.expected("an integer")),
+            len => number(cursor, sign + len),
+        }
+    }
+}
+
+/// A single decimal digit.
+pub fn digit() -> impl Parser<u8> {
+    |cursor: &mut Cursor| match cursor.rest().bytes().next() {
+        Some(b) if b.is_ascii_digit() => {
+            cursor.advance(1);
+            Ok(b - b'0')
+        }
+        _ => Err(cursor.expected("a digit")),
+    }
+}
+
+pub fn map<T, U>(parser: impl Parser<T>, f: impl Fn(T) -> U) -> impl Parser<U> {
+    move |cursor: &mut Cursor| parser(cursor).map(&f)
+}
+
+/// Like [`map`] for a fallible `f`, whose error is reported where the
+/// parsed value starts. The value was read all the same, so the
+/// combinators around do not backtrack on that error.
+pub fn try_map<T, U, E: fmt::Display>(
+    parser: impl Parser<T>,
+    f: impl Fn(T) -> Result<U, E>,
+) -> impl Parser<U> {
+    move |cursor: &mut Cursor| {
+        let start = *cursor;
+        f(parser(cursor)?).map_err(|e| ParseError {
+            offset: cursor.offset,
+            ..start.error(e.to_string())
+        })
+    }
+}
+
+/// `parser` if it matches here, else `None`.
+pub fn opt<T>(parser: impl Parser<T>) -> impl Parser<Option<T>> {
+    move |cursor: &mut Cursor| {
+        let start = *cursor;
+        match parser(cursor) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if failed_at(&e, &start) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+pub fn pair<A, B>(first: impl Parser<A>, second: impl Parser<B>) -> impl Parser<(A, B)> {
+    move |cursor: &mut Cursor| Ok((first(cursor)?, second(cursor)?))
+}
+
+/// `first` and `second` with `separator` between them, e.g. `3-5`.
+pub fn separated_pair<A, S, B>(
+    first: impl Parser<A>,
+    separator: impl Parser<S>,
+    second: impl Parser<B>,
+) -> impl Parser<(A, B)> {
+    move |cursor: &mut Cursor| {
+        let a = first(cursor)?;
+        separator(cursor)?;
+        Ok((a, second(cursor)?))
+    }
+}
+
+pub fn preceded<S, T>(prefix: impl Parser<S>, parser: impl Parser<T>) -> impl Parser<T> {
+    move |cursor: &mut Cursor| {
+        prefix(cursor)?;
+        parser(cursor)
+    }
+}
+
+pub fn terminated<T, S>(parser: impl Parser<T>, suffix: impl Parser<S>) -> impl Parser<T> {
+    move |cursor: &mut Cursor| {
+        let value = parser(cursor)?;
+        suffix(cursor)?;
+        Ok(value)
+    }
+}
+
+/// `parser` between `open` and `close`, e.g. `(1,3)`.
+pub fn delimited<O, T, C>(
+    open: impl Parser<O>,
+    parser: impl Parser<T>,
+    close: impl Parser<C>,
+) -> impl Parser<T> {
+    terminated(preceded(open, parser), close)
+}
+
+/// One or more of `parser` in a row.
+pub fn many1<T>(parser: impl Parser<T>) -> impl Parser<Vec<T>> {
+    move |cursor: &mut Cursor| {
+        let mut values = vec![parser(cursor)?];
+        loop {
+            let start = *cursor;
+            match parser(cursor) {
+                Ok(value) => values.push(value),
+                Err(e) if failed_at(&e, &start) => return Ok(values),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// One or more of `item` with `separator` between them, e.g. `1,2,3`. A
+/// separator not followed by an item is left unread.
+pub fn list<T, S>(item: impl Parser<T>, separator: impl Parser<S>) -> impl Parser<Vec<T>> {
+    move |cursor: &mut Cursor| {
+        let mut items = vec![item(cursor)?];
+        loop {
+            let before = *cursor;
+            if separator(cursor).is_err() {
+                *cursor = before;
+                return Ok(items);
+            }
+            let start = *cursor;
+            match item(cursor) {
+                Ok(value) => items.push(value),
+                Err(e) if failed_at(&e, &start) => {
+                    *cursor = before;
+                    return Ok(items);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// One `item` per line, up to a blank line or the end of the input. Every
+/// line in between must match.
+pub fn lines<T>(item: impl Parser<T>) -> impl Parser<Vec<T>> {
+    move |cursor: &mut Cursor| {
+        let mut items = vec![item(cursor)?];
+        loop {
+            let rest = cursor.rest();
+            if rest.is_empty() {
+                return Ok(items);
+            }
+            if !rest.starts_with('\n') {
+                return Err(cursor.expected("end of line"));
+            }
+            if rest[1..].is_empty() || rest[1..].starts_with('\n') {
+                return Ok(items);
+            }
+            cursor.advance(1);
+            items.push(item(cursor)?);
+        }
+    }
+}
+
+/// The empty line between two sections, with the line break before it.
+pub fn blank_line() -> impl Parser<()> {
+    map(tag("\n\n"), |_| ())
+}
+
+/// Two sections of the input separated by a blank line.
+pub fn sections<A, B>(first: impl Parser<A>, second: impl Parser<B>) -> impl Parser<(A, B)> {
+    separated_pair(first, blank_line(), second)
+}
+
+/// The cells of one row, up to the end of the line, or exactly `width` of
+/// them.
+fn row<T>(
+    cursor: &mut Cursor,
+    what: &str,
+    cell: &impl Fn(char) -> Option<T>,
+    width: Option<usize>,
+) -> Result<Vec<T>, ParseError> {
+    let mut cells = vec![];
+    while width.is_none_or(|width| cells.len() < width) {
+        let value = cursor.rest().chars().next().filter(|&c| c != '\n');
+        match value.and_then(cell) {
+            Some(value) => cells.push(value),
+            None if width.is_none() && !cells.is_empty() && cursor.is_at_line_end() => break,
+            None => return Err(cursor.expected(what)),
+        }
+        cursor.advance(value.unwrap().len_utf8());
+    }
+    if !cursor.is_at_line_end() {
+        return Err(cursor.expected("end of line"));
+    }
+    Ok(cells)
+}
+
+fn rows<T>(
+    what: &'static str,
+    cell: impl Fn(char) -> Option<T>,
+    rectangular: bool,
+) -> impl Parser<Vec<Vec<T>>> {
+    move |cursor: &mut Cursor| {
+        let first = row(cursor, what, &cell, None)?;
+        let width = rectangular.then_some(first.len());
+        let mut grid = vec![first];
+        while cursor.rest().starts_with('\n') {
+            let next = &cursor.rest()[1..];
+            if next.is_empty() || next.starts_with('\n') {
+                break;
+            }
+            cursor.advance(1);
+            grid.push(row(cursor, what, &cell, width)?);
+        }
+        Ok(grid)
+    }
+}
+
+/// Lines of one or more characters, each turned into a cell by `cell`,
+/// which describes the characters it accepts as `what`. Rows may differ in
+/// length.
+pub fn grid<T>(what: &'static str, cell: impl Fn(char) -> Option<T>) -> impl Parser<Vec<Vec<T>>> {
+    rows(what, cell, false)
+}
+
+/// Like [`grid`], with every row as long as the first.
+pub fn rectangular_grid<T>(
+    what: &'static str,
+    cell: impl Fn(char) -> Option<T>,
+) -> impl Parser<Vec<Vec<T>>> {
+    rows(what, cell, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error<T: fmt::Debug>(result: Result<T, ParseError>) -> (usize, usize, String) {
+        let e = result.unwrap_err();
+        (e.line, e.column, e.message)
+    }
+
+    #[test]
+    fn errors_point_at_line_and_column() {
+        let numbers = lines(uint::<u32>());
+        assert_eq!(complete(&numbers, "1\n22\n333\n").unwrap(), [1, 22, 333]);
+
+        assert_eq!(
+            error(complete(&numbers, "1\n2x\n3\n")),
+            (2, 2, "expected end of line, found 'x'".into())
+        );
+        assert_eq!(
+            error(complete(&numbers, "1\n\n3\n")),
+            (3, 1, "expected end of input, found '3'".into())
+        );
+        assert_eq!(
+            error(complete(&numbers, "7\n-8")),
+            (2, 1, "expected an unsigned integer, found '-8'".into())
+        );
+        assert_eq!(
+            error(complete(uint::<u8>(), "300")),
+            (
+                1,
+                1,
+                "invalid number '300': number too large to fit in target type".into()
+            )
+        );
+
+        // Columns count characters, not bytes
+        assert_eq!(
+            error(complete(pair(tag("é"), digit()), "éx")),
+            (1, 2, "expected a digit, found 'x'".into())
+        );
+    }
+
+    #[test]
+    fn reports_what_was_found() {
+        assert_eq!(
+            error(complete(tag("a"), "")),
+            (1, 1, "expected \"a\", found end of input".into())
+        );
+        assert_eq!(
+            error(complete(pair(tag("a"), tag("b")), "a\nb")),
+            (1, 2, "expected \"b\", found end of line".into())
+        );
+        assert_eq!(
+            error(complete(one_of("LR"), " L")),
+            (1, 1, "expected one of 'L', 'R', found ' '".into())
+        );
+    }
+
+    #[test]
+    fn opt_backtracks_only_before_reading() {
+        let signed = pair(opt(tag("-")), uint::<u32>());
+        assert_eq!(complete(&signed, "-5").unwrap(), (Some("-"), 5));
+        assert_eq!(complete(&signed, "5").unwrap(), (None, 5));
+
+        // `(1,2)` read part way fails as a whole
+        let point = opt(delimited(tag("("), list(uint::<u32>(), tag(",")), tag(")")));
+        assert_eq!(complete(&point, "(1,2)").unwrap(), Some(vec![1, 2]));
+        assert_eq!(
+            error(complete(&point, "(1,2")),
+            (1, 5, "expected \")\", found end of input".into())
+        );
+    }
+
+    #[test]
+    fn many1_stops_where_the_next_item_does_not_start() {
+        let bits = many1(one_of("01"));
+        assert_eq!(
+            complete(pair(&bits, tag("!")), "0110!").unwrap(),
+            (vec!['0', '1', '1', '0'], "!")
+        );
+        assert_eq!(
+            error(complete(&bits, "2")),
+            (1, 1, "expected one of '0', '1', found '2'".into())
+        );
+
+        // An item failing part way is an error, not the end of the run
+        let pairs = many1(preceded(tag("<"), terminated(digit(), tag(">"))));
+        assert_eq!(complete(&pairs, "<1><2>").unwrap(), [1, 2]);
+        assert_eq!(
+            error(complete(&pairs, "<1><2")),
+            (1, 6, "expected \">\", found end of input".into())
+        );
+    }
+
+    #[test]
+    fn list_leaves_a_trailing_separator_unread() {
+        let numbers = list(uint::<u32>(), tag(","));
+        assert_eq!(
+            complete(terminated(&numbers, tag(",")), "1,2,3,").unwrap(),
+            [1, 2, 3]
+        );
+        assert_eq!(
+            complete(separated_pair(&numbers, tag(", "), one_of("x")), "1,2, x").unwrap(),
+            (vec![1, 2], 'x')
+        );
+        assert_eq!(
+            error(complete(&numbers, "1,2,")),
+            (1, 4, "expected end of input, found ','".into())
+        );
+
+        // An item failing part way is reported where it failed
+        let ranges = list(
+            separated_pair(uint::<u32>(), tag("-"), uint::<u32>()),
+            tag(","),
+        );
+        assert_eq!(complete(&ranges, "1-2,3-4").unwrap(), [(1, 2), (3, 4)]);
+        assert_eq!(
+            error(complete(&ranges, "1-2,3-")),
+            (
+                1,
+                7,
+                "expected an unsigned integer, found end of input".into()
+            )
+        );
+    }
+
+    #[test]
+    fn try_map_errors_are_not_backtracked() {
+        let small = try_map(uint::<u32>(), |n| match n {
+            n if n < 10 => Ok(n),
+            n => Err(format!("{} is too large", n)),
+        });
+        let numbers = list(small, tag(","));
+        assert_eq!(complete(&numbers, "1,2").unwrap(), [1, 2]);
+        // Reported at the value, not as a separator left over
+        assert_eq!(
+            error(complete(&numbers, "1,23,4")),
+            (1, 3, "23 is too large".into())
+        );
+    }
+
+    #[test]
+    fn complete_allows_only_trailing_newlines() {
+        assert_eq!(complete(uint::<u32>(), "12").unwrap(), 12);
+        assert_eq!(complete(uint::<u32>(), "12\n\n\n").unwrap(), 12);
+        assert_eq!(
+            error(complete(uint::<u32>(), "12 ")),
+            (1, 3, "expected end of input, found ' '".into())
+        );
+        assert_eq!(
+            error(complete(uint::<u32>(), "12\n\n3")),
+            (3, 1, "expected end of input, found '3'".into())
+        );
+    }
+
+    #[test]
+    fn sections_and_grids() {
+        let input = "1-3\n5-7\n\n2\n6\n";
+        let ranges = lines(separated_pair(uint::<u32>(), tag("-"), uint::<u32>()));
+        let (ranges, ids) = complete(sections(ranges, lines(uint::<u32>())), input).unwrap();
+        assert_eq!(ranges, [(1, 3), (5, 7)]);
+        assert_eq!(ids, [2, 6]);
+
+        let cells = |c| (c == '#').then_some(true).or((c == '.').then_some(false));
+        assert_eq!(
+            complete(grid("a cell", cells), "#.\n.\n").unwrap(),
+            [vec![true, false], vec![false]]
+        );
+        assert_eq!(
+            error(complete(rectangular_grid("a cell", cells), "#.\n.\n")),
+            (2, 2, "expected a cell, found end of line".into())
+        );
+        assert_eq!(
+            error(complete(rectangular_grid("a cell", cells), "#.\n.#.\n")),
+            (2, 3, "expected end of line, found '.'".into())
+        );
+        assert_eq!(
+            error(complete(grid("a cell", cells), "#.\n.x\n")),
+            (2, 2, "expected a cell, found 'x'".into())
+        );
+    }
+}
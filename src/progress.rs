@@ -0,0 +1,62 @@
+//! A progress bar on stderr for long computations.
+//!
+//! Nothing is drawn unless stderr is a terminal, so piped or captured output
+//! stays clean. Ticks may come from any thread.
+
+use std::{
+    io::{self, IsTerminal, Write},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+const WIDTH: usize = 30;
+
+pub struct Progress {
+    label: &'static str,
+    total: usize,
+    done: AtomicUsize,
+    visible: bool,
+}
+
+impl Progress {
+    pub fn new(label: &'static str, total: usize) -> Self {
+        let progress = Progress {
+            label,
+            total,
+            done: AtomicUsize::new(0),
+            visible: io::stderr().is_terminal(),
+        };
+        progress.draw(0);
+        progress
+    }
+
+    /// Count one more item as done.
+    pub fn tick(&self) {
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        self.draw(done);
+    }
+
+    /// Erase the bar.
+    pub fn finish(&self) {
+        if self.visible {
+            eprint!("\r\x1b[2K");
+            let _ = io::stderr().flush();
+        }
+    }
+
+    fn draw(&self, done: usize) {
+        if !self.visible {
+            return;
+        }
+
+        let filled = (done * WIDTH).checked_div(self.total).unwrap_or(WIDTH);
+        eprint!(
+            "\r{} [{}{}] {}/{}",
+            self.label,
+            "#".repeat(filled),
+            ".".repeat(WIDTH - filled),
+            done,
+            self.total
+        );
+        let _ = io::stderr().flush();
+    }
+}
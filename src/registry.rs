@@ -0,0 +1,44 @@
+//! Lookup of the solutions by year and day.
+//!
+//! Each year lives in its own module (`src/<year>/mod.rs`) with a table of
+//! its days; helpers shared between years live at the crate root.
+
+use crate::solution::Solution;
+
+pub type Solutions = &'static [(u32, &'static dyn Solution)];
+
+// One entry per line, kept in order by `main new`
+#[rustfmt::skip]
+static YEARS: &[(u32, Solutions)] = &[
+    (2025, crate::y2025::SOLUTIONS),
+];
+
+/// Years with at least one registered day, in order.
+pub fn years() -> Vec<u32> {
+    YEARS
+        .iter()
+        .filter(|(_, solutions)| !solutions.is_empty())
+        .map(|(year, _)| *year)
+        .collect()
+}
+
+/// Solutions of a year, keyed by day.
+pub fn year(year: u32) -> Option<Solutions> {
+    YEARS.iter().find(|(y, _)| *y == year).map(|(_, s)| *s)
+}
+
+pub fn solution(year: u32, day: u32) -> Option<&'static dyn Solution> {
+    self::year(year)?
+        .iter()
+        .find(|(d, _)| *d == day)
+        .map(|(_, solution)| *solution)
+}
+
+/// Every registered solution as `(year, day, solution)`.
+pub fn all() -> impl Iterator<Item = (u32, u32, &'static dyn Solution)> {
+    YEARS.iter().flat_map(|(year, solutions)| {
+        solutions
+            .iter()
+            .map(move |(day, solution)| (*year, *day, *solution))
+    })
+}
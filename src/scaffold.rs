@@ -0,0 +1,300 @@
+//! Generating the files of a new puzzle day.
+//!
+//! A day is added by writing `src/<year>/day_<day>.rs` from a template,
+//! declaring it in `src/<year>/mod.rs` and listing it in that year's
+//! `SOLUTIONS`. A new year additionally gets its `mod.rs`, a module in
+//! `src/lib.rs` and an entry in the `YEARS` of `src/registry.rs`.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::solution::Error;
+
+fn day_template(day: u32) -> String {
+    format!(
+        r#"use crate::solution::{{Answer, Error, Example, Solution}};
+
+// Paste the example from the puzzle text here, and its answers below
+const EXAMPLE: &str = "";
+
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+    fn implemented(&self) -> [bool; 2] {{
+        [false, false]
+    }}
+
+    fn part_1(&self, input: &str) -> Result<Answer, Error> {{
+        let _ = input;
+        Err("Part 1 is not implemented yet".into())
+    }}
+
+    fn part_2(&self, input: &str) -> Result<Answer, Error> {{
+        let _ = input;
+        Err("Part 2 is not implemented yet".into())
+    }}
+
+    fn examples(&self) -> &'static [Example] {{
+        &[Example {{
+            name: "example",
+            input: EXAMPLE,
+            part_1: None,
+            part_2: None,
+            solution: None,
+        }}]
+    }}
+}}
+"#
+    )
+}
+
+fn year_template(year: u32) -> String {
+    format!(
+        r#"//! Solutions for Advent of Code {year}.
+
+use crate::solution::Solution;
+
+// One entry per line, kept in order by `main new`
+#[rustfmt::skip]
+pub static SOLUTIONS: &[(u32, &dyn Solution)] = &[
+];
+"#
+    )
+}
+
+/// Day or year a line declares or registers, e.g. `pub mod day_3;`,
+/// `    (3, &day_3::Day3),` or `    (2025, crate::y2025::SOLUTIONS),`.
+fn line_day(line: &str, prefix: &str) -> Option<u32> {
+    let rest = line.trim_start().strip_prefix(prefix)?;
+    let digits = rest
+        .chars()
+        .take_while(|ch| ch.is_ascii_digit())
+        .collect::<String>();
+    digits.parse().ok()
+}
+
+/// Insert `new_line` among the lines starting with `prefix`, keeping them
+/// ordered by `key` of their day. Without such lines it goes right after
+/// `anchor`, preceded by an empty line if `separate` is set.
+fn insert_ordered<K: Ord>(
+    contents: &str,
+    (prefix, key): (&str, impl Fn(u32) -> K),
+    day: u32,
+    new_line: &str,
+    (anchor, separate): (&str, bool),
+) -> Result<String, Error> {
+    let mut lines = contents.lines().map(String::from).collect::<Vec<String>>();
+
+    let existing = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| line_day(line, prefix).map(|d| (i, d)))
+        .collect::<Vec<(usize, u32)>>();
+
+    let index = match existing.iter().find(|(_, d)| key(*d) > key(day)) {
+        Some((i, _)) => *i,
+        None => match existing.last() {
+            Some((i, _)) => i + 1,
+            None => {
+                let index = lines
+                    .iter()
+                    .position(|line| line.starts_with(anchor))
+                    .ok_or_else(|| format!("'{}' not found", anchor))?
+                    + 1;
+                if separate {
+                    lines.insert(index, String::new());
+                    index + 1
+                } else {
+                    index
+                }
+            }
+        },
+    };
+
+    lines.insert(index, new_line.to_string());
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn register_year(root: &Path, year: u32) -> Result<Vec<PathBuf>, Error> {
+    let lib = root.join("src/lib.rs");
+    let mut contents = fs::read_to_string(&lib)?;
+    contents.push_str(&format!(
+        "\n#[path = \"{year}/mod.rs\"]\npub mod y{year};\n"
+    ));
+    fs::write(&lib, contents)?;
+
+    let registry = root.join("src/registry.rs");
+    let contents = fs::read_to_string(&registry)?;
+    let contents = insert_ordered(
+        &contents,
+        ("(", |y| y),
+        year,
+        &format!("    ({year}, crate::y{year}::SOLUTIONS),"),
+        ("static YEARS", false),
+    )?;
+    fs::write(&registry, contents)?;
+
+    Ok(vec![lib, registry])
+}
+
+/// Create and register the solution of a new day under `root`, returning
+/// the files created or modified.
+pub fn new_day(root: &Path, year: u32, day: u32) -> Result<Vec<PathBuf>, Error> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("Invalid day: {}", day).into());
+    }
+
+    let year_dir = root.join("src").join(year.to_string());
+    let day_file = year_dir.join(format!("day_{}.rs", day));
+    if day_file.exists() {
+        return Err(format!("{} already exists", day_file.display()).into());
+    }
+
+    let mut touched = vec![];
+
+    let year_mod = year_dir.join("mod.rs");
+    if !year_mod.exists() {
+        fs::create_dir_all(&year_dir)?;
+        fs::write(&year_mod, year_template(year))?;
+        touched.push(year_mod.clone());
+        touched.extend(register_year(root, year)?);
+    }
+
+    fs::write(&day_file, day_template(day))?;
+    touched.push(day_file);
+
+    let contents = fs::read_to_string(&year_mod)?;
+    let contents = insert_ordered(
+        &contents,
+        // Same order as rustfmt sorts module declarations
+        ("pub mod day_", |d| format!("day_{d}")),
+        day,
+        &format!("pub mod day_{day};"),
+        ("use crate::solution::Solution;", true),
+    )?;
+    let contents = insert_ordered(
+        &contents,
+        ("(", |d| d),
+        day,
+        &format!("    ({day}, &day_{day}::Day{day}),"),
+        ("pub static SOLUTIONS", false),
+    )?;
+    fs::write(&year_mod, contents)?;
+    if !touched.contains(&year_mod) {
+        touched.push(year_mod);
+    }
+
+    // No input file: an existing one, even empty, counts as cached and
+    // would never be fetched
+    Ok(touched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A copy of the registration files of this crate under a fresh root.
+    fn temp_root(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("aoc-scaffold-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/2025")).unwrap();
+        fs::write(root.join("src/lib.rs"), include_str!("lib.rs")).unwrap();
+        fs::write(root.join("src/registry.rs"), include_str!("registry.rs")).unwrap();
+        fs::write(root.join("src/2025/mod.rs"), include_str!("2025/mod.rs")).unwrap();
+        root
+    }
+
+    fn read(root: &Path, path: &str) -> String {
+        fs::read_to_string(root.join(path)).unwrap()
+    }
+
+    /// Index of the line equal to `line` once trimmed.
+    fn line_index(contents: &str, line: &str) -> usize {
+        contents
+            .lines()
+            .position(|l| l.trim() == line)
+            .unwrap_or_else(|| panic!("'{}' not in:\n{}", line, contents))
+    }
+
+    #[test]
+    fn adds_a_day_to_an_existing_year() {
+        let root = temp_root("day");
+
+        let touched = new_day(&root, 2025, 11).unwrap();
+        assert_eq!(
+            touched,
+            [
+                root.join("src/2025/day_11.rs"),
+                root.join("src/2025/mod.rs")
+            ]
+        );
+        assert!(read(&root, "src/2025/day_11.rs").contains("pub struct Day11;"));
+        assert!(!root.join("assets").exists());
+
+        // Declared in rustfmt's order and registered in day order
+        let year = read(&root, "src/2025/mod.rs");
+        let declared = line_index(&year, "pub mod day_11;");
+        assert_eq!(declared, line_index(&year, "pub mod day_10;") + 1);
+        assert_eq!(declared + 1, line_index(&year, "pub mod day_2;"));
+        let registered = line_index(&year, "(11, &day_11::Day11),");
+        assert_eq!(registered, line_index(&year, "(9, &day_9::Day9),") + 2);
+        assert_eq!(registered + 1, line_index(&year, "];"));
+
+        // Nothing outside the year changes
+        assert_eq!(read(&root, "src/lib.rs"), include_str!("lib.rs"));
+        assert_eq!(read(&root, "src/registry.rs"), include_str!("registry.rs"));
+
+        assert!(new_day(&root, 2025, 11).is_err());
+        assert!(new_day(&root, 2025, 26).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn adds_a_new_year() {
+        let root = temp_root("year");
+
+        new_day(&root, 2024, 7).unwrap();
+        new_day(&root, 2024, 12).unwrap();
+        new_day(&root, 2024, 3).unwrap();
+
+        let year = read(&root, "src/2024/mod.rs");
+        let declarations = year
+            .lines()
+            .filter(|line| line.starts_with("pub mod "))
+            .collect::<Vec<&str>>();
+        assert_eq!(
+            declarations,
+            ["pub mod day_12;", "pub mod day_3;", "pub mod day_7;"]
+        );
+        let entries = year
+            .lines()
+            .filter(|line| line.starts_with("    ("))
+            .collect::<Vec<&str>>();
+        assert_eq!(
+            entries,
+            [
+                "    (3, &day_3::Day3),",
+                "    (7, &day_7::Day7),",
+                "    (12, &day_12::Day12),"
+            ]
+        );
+
+        let lib = read(&root, "src/lib.rs");
+        assert!(lib.ends_with("\n#[path = \"2024/mod.rs\"]\npub mod y2024;\n"));
+        assert_eq!(lib.matches("pub mod y2024;").count(), 1);
+
+        let registry = read(&root, "src/registry.rs");
+        let registered = line_index(&registry, "(2024, crate::y2024::SOLUTIONS),");
+        assert_eq!(
+            registered + 1,
+            line_index(&registry, "(2025, crate::y2025::SOLUTIONS),")
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
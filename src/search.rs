@@ -0,0 +1,482 @@
+//! Generic shortest-path searches over implicit graphs.
+//!
+//! A puzzle describes its graph as a [`StateSpace`]: where to start, which
+//! states are goals, and the successors of a state with the cost of each
+//! move. [`bfs`], [`dijkstra`] and [`astar`] then find a cheapest path to a
+//! goal, with statistics on the work done. [`Limits`] stop a search that
+//! takes too long or visits too many states.
+//!
+//! States are stored once in a table and referred to by index, so the
+//! bookkeeping for paths costs a few integers per state.
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+pub trait StateSpace {
+    type State: Clone + Eq + Hash;
+
+    fn start(&self) -> Self::State;
+
+    fn is_goal(&self, state: &Self::State) -> bool;
+
+    /// The states reachable in one move, with the cost of the move.
+    fn successors(&self, state: &Self::State) -> impl Iterator<Item = (Self::State, u64)>;
+
+    /// A lower bound on the cost from `state` to a goal, used by [`astar`].
+    /// It must never overestimate, or the path found may not be cheapest.
+    /// `None` means no goal is reachable, and the state is dropped.
+    fn heuristic(&self, _state: &Self::State) -> Option<u64> {
+        Some(0)
+    }
+}
+
+/// Limits after which a search gives up with [`Outcome::Aborted`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Limits {
+    pub time: Option<Duration>,
+    /// Distinct states reached.
+    pub states: Option<usize>,
+}
+
+impl Limits {
+    pub const NONE: Limits = Limits {
+        time: None,
+        states: None,
+    };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome<S> {
+    /// A cheapest path from the start to a goal, both included.
+    Found {
+        cost: u64,
+        path: Vec<S>,
+    },
+    /// Every reachable state was expanded without meeting a goal.
+    Unreachable,
+    Aborted,
+}
+
+/// Work done by a search.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// States whose successors were generated.
+    pub expanded: u64,
+    /// Distinct states reached.
+    pub visited: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Search<S> {
+    pub outcome: Outcome<S>,
+    pub stats: Stats,
+}
+
+impl<S> Search<S> {
+    /// Cost of the path found, if any.
+    pub fn cost(&self) -> Option<u64> {
+        match self.outcome {
+            Outcome::Found { cost, .. } => Some(cost),
+            _ => None,
+        }
+    }
+}
+
+/// Parent of the start state.
+const NO_PARENT: usize = usize::MAX;
+
+/// Every state reached, with the move that reached it most cheaply.
+struct Table<S> {
+    states: Vec<S>,
+    parents: Vec<usize>,
+    costs: Vec<u64>,
+    index: HashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash> Table<S> {
+    fn new() -> Self {
+        Table {
+            states: vec![],
+            parents: vec![],
+            costs: vec![],
+            index: HashMap::new(),
+        }
+    }
+
+    /// Whether reaching `state` at `cost` is the first or a cheaper way.
+    fn improves(&self, state: &S, cost: u64) -> bool {
+        self.index.get(state).is_none_or(|&i| cost < self.costs[i])
+    }
+
+    /// Record that `state` is reached at `cost` from `parent`. Returns its
+    /// index if that is the first or a cheaper way to reach it.
+    fn reach(&mut self, state: S, cost: u64, parent: usize) -> Option<usize> {
+        match self.index.get(&state) {
+            Some(&i) if self.costs[i] <= cost => None,
+            Some(&i) => {
+                self.costs[i] = cost;
+                self.parents[i] = parent;
+                Some(i)
+            }
+            None => {
+                let i = self.states.len();
+                self.index.insert(state.clone(), i);
+                self.states.push(state);
+                self.parents.push(parent);
+                self.costs.push(cost);
+                Some(i)
+            }
+        }
+    }
+
+    fn path(&self, mut i: usize) -> Vec<S> {
+        let mut path = vec![self.states[i].clone()];
+        while self.parents[i] != NO_PARENT {
+            i = self.parents[i];
+            path.push(self.states[i].clone());
+        }
+        path.reverse();
+        path
+    }
+
+    fn found(&self, i: usize) -> Outcome<S> {
+        Outcome::Found {
+            cost: self.costs[i],
+            path: self.path(i),
+        }
+    }
+}
+
+/// Checks the limits, reading the clock only every so many expansions.
+struct Watch {
+    start: Instant,
+    limits: Limits,
+}
+
+impl Watch {
+    fn new(limits: &Limits) -> Self {
+        Watch {
+            start: Instant::now(),
+            limits: *limits,
+        }
+    }
+
+    fn exceeded(&self, stats: &Stats) -> bool {
+        self.limits
+            .states
+            .is_some_and(|states| stats.visited > states)
+            || (stats.expanded.is_multiple_of(1024)
+                && self
+                    .limits
+                    .time
+                    .is_some_and(|time| self.start.elapsed() > time))
+    }
+}
+
+/// Fewest moves to a goal, ignoring the costs of the moves. The cost found
+/// is the number of moves.
+pub fn bfs<P: StateSpace>(space: &P, limits: &Limits) -> Search<P::State> {
+    let watch = Watch::new(limits);
+    let mut stats = Stats::default();
+    let mut table = Table::new();
+    let mut queue = VecDeque::new();
+
+    let start = table.reach(space.start(), 0, NO_PARENT).unwrap();
+    queue.push_back(start);
+
+    while let Some(i) = queue.pop_front() {
+        stats.visited = table.states.len();
+        if space.is_goal(&table.states[i]) {
+            return Search {
+                outcome: table.found(i),
+                stats,
+            };
+        }
+        if watch.exceeded(&stats) {
+            return Search {
+                outcome: Outcome::Aborted,
+                stats,
+            };
+        }
+
+        stats.expanded += 1;
+        let state = table.states[i].clone();
+        let cost = table.costs[i] + 1;
+        for (next, _) in space.successors(&state) {
+            // Breadth first, the first way to reach a state is the shortest
+            if !table.index.contains_key(&next)
+                && let Some(j) = table.reach(next, cost, i)
+            {
+                queue.push_back(j);
+            }
+        }
+    }
+
+    stats.visited = table.states.len();
+    Search {
+        outcome: Outcome::Unreachable,
+        stats,
+    }
+}
+
+/// An entry of the priority queue, cheapest estimate first and, among
+/// equal estimates, the one that went furthest.
+#[derive(PartialEq, Eq)]
+struct Queued {
+    estimate: u64,
+    cost: u64,
+    index: usize,
+}
+
+impl Ord for Queued {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then(self.cost.cmp(&other.cost))
+    }
+}
+
+impl PartialOrd for Queued {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn best_first<P: StateSpace>(
+    space: &P,
+    limits: &Limits,
+    heuristic: impl Fn(&P::State) -> Option<u64>,
+) -> Search<P::State> {
+    let watch = Watch::new(limits);
+    let mut stats = Stats::default();
+    let mut table = Table::new();
+    let mut heap = BinaryHeap::new();
+
+    let start = space.start();
+    if let Some(estimate) = heuristic(&start) {
+        let index = table.reach(start, 0, NO_PARENT).unwrap();
+        heap.push(Queued {
+            estimate,
+            cost: 0,
+            index,
+        });
+    }
+
+    while let Some(Queued { cost, index, .. }) = heap.pop() {
+        // Left over from before a cheaper way to the state was found
+        if cost > table.costs[index] {
+            continue;
+        }
+
+        stats.visited = table.states.len();
+        if space.is_goal(&table.states[index]) {
+            return Search {
+                outcome: table.found(index),
+                stats,
+            };
+        }
+        if watch.exceeded(&stats) {
+            return Search {
+                outcome: Outcome::Aborted,
+                stats,
+            };
+        }
+
+        stats.expanded += 1;
+        let state = table.states[index].clone();
+        for (next, step) in space.successors(&state) {
+            let next_cost = cost + step;
+            // Estimating is often the costly part, so only for improvements
+            if !table.improves(&next, next_cost) {
+                continue;
+            }
+            let Some(estimate) = heuristic(&next) else {
+                continue;
+            };
+            if let Some(next_index) = table.reach(next, next_cost, index) {
+                heap.push(Queued {
+                    estimate: next_cost + estimate,
+                    cost: next_cost,
+                    index: next_index,
+                });
+            }
+        }
+    }
+
+    stats.visited = table.states.len();
+    Search {
+        outcome: Outcome::Unreachable,
+        stats,
+    }
+}
+
+/// Cheapest path to a goal, for non-negative move costs.
+pub fn dijkstra<P: StateSpace>(space: &P, limits: &Limits) -> Search<P::State> {
+    best_first(space, limits, |_| Some(0))
+}
+
+/// Cheapest path to a goal, guided by [`StateSpace::heuristic`].
+pub fn astar<P: StateSpace>(space: &P, limits: &Limits) -> Search<P::State> {
+    best_first(space, limits, |state| space.heuristic(state))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A graph given by its weighted edges, searched from 0.
+    struct Graph {
+        edges: &'static [(usize, usize, u64)],
+        goal: usize,
+        /// States the heuristic rules out.
+        dead: &'static [usize],
+    }
+
+    impl StateSpace for Graph {
+        type State = usize;
+
+        fn start(&self) -> usize {
+            0
+        }
+
+        fn is_goal(&self, state: &usize) -> bool {
+            *state == self.goal
+        }
+
+        fn successors(&self, state: &usize) -> impl Iterator<Item = (usize, u64)> {
+            self.edges
+                .iter()
+                .filter(move |(from, _, _)| from == state)
+                .map(|&(_, to, cost)| (to, cost))
+        }
+
+        fn heuristic(&self, state: &usize) -> Option<u64> {
+            (!self.dead.contains(state)).then_some(0)
+        }
+    }
+
+    /// 0 reaches 1 directly at a high price, or cheaply through 2; 3 is the
+    /// goal and 4 leads nowhere.
+    const EDGES: &[(usize, usize, u64)] = &[
+        (0, 1, 10),
+        (0, 2, 1),
+        (2, 1, 1),
+        (1, 3, 1),
+        (2, 3, 5),
+        (0, 4, 1),
+    ];
+
+    fn found(search: &Search<usize>) -> (u64, &[usize]) {
+        match &search.outcome {
+            Outcome::Found { cost, path } => (*cost, path),
+            outcome => panic!("expected a path, got {:?}", outcome),
+        }
+    }
+
+    #[test]
+    fn dijkstra_takes_the_cheaper_way_found_later() {
+        let graph = Graph {
+            edges: EDGES,
+            goal: 3,
+            dead: &[],
+        };
+
+        let search = dijkstra(&graph, &Limits::NONE);
+        assert_eq!(found(&search), (3, &[0, 2, 1, 3][..]));
+        assert_eq!(search.cost(), Some(3));
+
+        // Fewest moves, whatever they cost
+        assert_eq!(found(&bfs(&graph, &Limits::NONE)), (2, &[0, 1, 3][..]));
+    }
+
+    #[test]
+    fn astar_drops_states_without_an_estimate() {
+        let graph = Graph {
+            edges: EDGES,
+            goal: 3,
+            dead: &[1, 4],
+        };
+
+        let search = astar(&graph, &Limits::NONE);
+        assert_eq!(found(&search), (6, &[0, 2, 3][..]));
+        // 1 and 4 were never stored
+        assert_eq!(search.stats.visited, 3);
+
+        let graph = Graph {
+            edges: EDGES,
+            goal: 3,
+            dead: &[0],
+        };
+        assert_eq!(astar(&graph, &Limits::NONE).outcome, Outcome::Unreachable);
+    }
+
+    #[test]
+    fn reports_an_unreachable_goal() {
+        let graph = Graph {
+            edges: EDGES,
+            goal: 5,
+            dead: &[],
+        };
+
+        for search in [
+            bfs(&graph, &Limits::NONE),
+            dijkstra(&graph, &Limits::NONE),
+            astar(&graph, &Limits::NONE),
+        ] {
+            assert_eq!(search.outcome, Outcome::Unreachable);
+            assert_eq!(search.cost(), None);
+            assert_eq!(search.stats.visited, 5);
+            assert_eq!(search.stats.expanded, 5);
+        }
+    }
+
+    #[test]
+    fn start_is_a_path_of_its_own() {
+        let graph = Graph {
+            edges: EDGES,
+            goal: 0,
+            dead: &[],
+        };
+
+        assert_eq!(found(&bfs(&graph, &Limits::NONE)), (0, &[0][..]));
+        assert_eq!(found(&dijkstra(&graph, &Limits::NONE)), (0, &[0][..]));
+    }
+
+    /// Counting up forever, looking for a goal that is never reached.
+    struct Endless;
+
+    impl StateSpace for Endless {
+        type State = u64;
+
+        fn start(&self) -> u64 {
+            0
+        }
+
+        fn is_goal(&self, _state: &u64) -> bool {
+            false
+        }
+
+        fn successors(&self, state: &u64) -> impl Iterator<Item = (u64, u64)> {
+            [(state + 1, 1), (state + 2, 1)].into_iter()
+        }
+    }
+
+    #[test]
+    fn aborts_past_the_states_limit() {
+        let limits = Limits {
+            time: None,
+            states: Some(100),
+        };
+
+        for search in [bfs(&Endless, &limits), dijkstra(&Endless, &limits)] {
+            assert_eq!(search.outcome, Outcome::Aborted);
+            assert!(search.stats.visited > 100);
+            assert!(search.stats.visited <= 102);
+        }
+    }
+}
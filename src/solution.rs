@@ -0,0 +1,210 @@
+//! The interface every puzzle solution implements.
+
+use std::{fmt, io::Write};
+
+use crate::{bigint::BigInt, check::CrossCheck, generate::Generator, normalise::Normalisation};
+
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+/// Writes a machine-readable trace of a run on the input, for `run --trace`.
+pub type Tracer = fn(&str, &mut dyn Write) -> Result<(), Error>;
+
+/// A puzzle answer, printed the way it is submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i128),
+    Big(BigInt),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Answer::Int(value as i128)
+            }
+        })*
+    };
+}
+
+answer_from_int!(i32, i64, i128, u32, u64, usize);
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        match i128::try_from(value) {
+            Ok(value) => Answer::Int(value),
+            Err(_) => Answer::Big(BigInt::from(value)),
+        }
+    }
+}
+
+/// Small enough answers are kept as [`Answer::Int`], so they compare equal
+/// regardless of how they were computed.
+impl From<BigInt> for Answer {
+    fn from(value: BigInt) -> Self {
+        match value.to_i128() {
+            Some(value) => Answer::Int(value),
+            None => Answer::Big(value),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+/// A sample input from the puzzle text with its expected answers, checked by
+/// `main run --example` and by the `examples` test.
+pub struct Example {
+    pub name: &'static str,
+    pub input: &'static str,
+    pub part_1: Option<&'static str>,
+    pub part_2: Option<&'static str>,
+    /// Solution configured for this example, if not the registered one.
+    pub solution: Option<&'static dyn Solution>,
+}
+
+/// Outcome of one part of an example.
+#[derive(Debug)]
+pub enum Check {
+    /// No expected answer is given for this part.
+    Skipped,
+    Passed(Answer),
+    Failed {
+        expected: &'static str,
+        actual: Result<Answer, String>,
+    },
+}
+
+impl Example {
+    /// Run `part` (1 or 2) on the example and compare with the expected answer.
+    pub fn check(&self, registered: &dyn Solution, part: u8) -> Check {
+        let solution = self.solution.unwrap_or(registered);
+
+        let expected = match part {
+            1 => self.part_1,
+            _ => self.part_2,
+        };
+        let Some(expected) = expected else {
+            return Check::Skipped;
+        };
+
+        match solution.solve(part, self.input) {
+            Ok(answer) if answer.to_string() == expected => Check::Passed(answer),
+            actual => Check::Failed {
+                expected,
+                actual: actual.map_err(|e| e.to_string()),
+            },
+        }
+    }
+}
+
+pub trait Solution: Sync {
+    fn part_1(&self, input: &str) -> Result<Answer, Error>;
+
+    fn part_2(&self, input: &str) -> Result<Answer, Error>;
+
+    /// Run part 1 or 2.
+    fn solve(&self, part: u8, input: &str) -> Result<Answer, Error> {
+        match part {
+            1 => self.part_1(input),
+            2 => self.part_2(input),
+            _ => Err(format!("Invalid part: {}", part).into()),
+        }
+    }
+
+    /// Do the work both parts need, like parsing, once for `input`. By
+    /// default nothing is shared and each part starts from the text.
+    fn prepare<'a>(&'a self, input: &'a str) -> Result<Box<dyn Prepared + 'a>, Error> {
+        Ok(Box::new(Unprepared {
+            solution: self,
+            input,
+        }))
+    }
+
+    /// How the input is cleaned up before it reaches the parts. Days whose
+    /// columns matter keep their spaces.
+    fn normalisation(&self) -> Normalisation {
+        Normalisation::Full
+    }
+
+    /// Which of the two parts are solved, as listed by `main list`.
+    fn implemented(&self) -> [bool; 2] {
+        [true, true]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    /// Random inputs for stress tests, printed by `main generate`.
+    fn generator(&self) -> Option<Generator> {
+        None
+    }
+
+    /// Reference solvers to cross-check the optimised ones with `main check`.
+    fn cross_checks(&self) -> &'static [CrossCheck] {
+        &[]
+    }
+
+    /// A trace of the run to inspect with other tools, written alone by
+    /// `run --trace`.
+    fn tracer(&self) -> Option<Tracer> {
+        None
+    }
+
+    /// Day-specific diagnostics, written by `run --debug`.
+    fn debug(&self, _input: &str, _out: &mut dyn Write) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// An input made ready for either part by [`Solution::prepare`].
+pub trait Prepared {
+    fn part_1(&self) -> Result<Answer, Error>;
+
+    fn part_2(&self) -> Result<Answer, Error>;
+
+    /// Run part 1 or 2.
+    fn solve(&self, part: u8) -> Result<Answer, Error> {
+        match part {
+            1 => self.part_1(),
+            2 => self.part_2(),
+            _ => Err(format!("Invalid part: {}", part).into()),
+        }
+    }
+}
+
+/// The input as given, for solutions that share nothing between parts.
+struct Unprepared<'a, S: ?Sized> {
+    solution: &'a S,
+    input: &'a str,
+}
+
+impl<S: Solution + ?Sized> Prepared for Unprepared<'_, S> {
+    fn part_1(&self) -> Result<Answer, Error> {
+        self.solution.part_1(self.input)
+    }
+
+    fn part_2(&self) -> Result<Answer, Error> {
+        self.solution.part_2(self.input)
+    }
+}
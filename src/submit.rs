@@ -0,0 +1,342 @@
+//! Submitting answers, with a local ledger of the verdicts.
+//!
+//! Every verdict is recorded in a [`Ledger`], so an answer already judged is
+//! never sent again, and neither is a number at or beyond one that was too
+//! high or too low. Answers are sent through a [`Transport`]: the puzzle
+//! site over HTTP, or a fake one in tests.
+
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{bigint::BigInt, inputs::curl};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, with no hint which way.
+    Wrong,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too high" => Ok(Verdict::TooHigh),
+            "too low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            _ => Err(format!("Unknown verdict: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Io(PathBuf, io::Error),
+    /// A ledger line that cannot be read.
+    Ledger {
+        path: PathBuf,
+        line: usize,
+    },
+    /// The site takes no answer for a while after a wrong one.
+    TooSoon(String),
+    /// The site will not judge the part: it is solved already, though not in
+    /// the ledger, or it is part 2 and part 1 is not solved yet. The site
+    /// tells neither apart.
+    AlreadySolved,
+    /// The request failed or its response was not understood.
+    Transport(String),
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            SubmitError::Ledger { path, line } => {
+                write!(f, "{}:{}: invalid ledger entry", path.display(), line)
+            }
+            SubmitError::TooSoon(wait) => write!(f, "Answered too recently, {}", wait),
+            SubmitError::AlreadySolved => {
+                write!(f, "Part already solved, or not unlocked yet")
+            }
+            SubmitError::Transport(e) => write!(f, "Failed to submit answer: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SubmitError {}
+
+/// Where answers are sent to be judged.
+pub trait Transport {
+    fn submit(&self, year: u32, day: u32, part: u8, answer: &str) -> Result<Verdict, SubmitError>;
+}
+
+/// The puzzle site, posting to `<base_url>/<year>/day/<day>/answer` with a
+/// session cookie.
+pub struct Http {
+    base_url: String,
+    session: String,
+}
+
+impl Http {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Http {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    /// `AOC_SESSION` and optionally `AOC_SUBMIT_URL`, like the input source.
+    pub fn from_env() -> Result<Self, SubmitError> {
+        let session = std::env::var("AOC_SESSION").map_err(|_| {
+            SubmitError::Transport("no session configured (set AOC_SESSION)".into())
+        })?;
+        let url = std::env::var("AOC_SUBMIT_URL")
+            .unwrap_or_else(|_| crate::inputs::Http::DEFAULT_URL.to_string());
+        Ok(Http::new(&url, &session))
+    }
+}
+
+impl Transport for Http {
+    fn submit(&self, year: u32, day: u32, part: u8, answer: &str) -> Result<Verdict, SubmitError> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let level = format!("level={}", part);
+        let answer = format!("answer={}", answer);
+        let page = curl(
+            &url,
+            &self.session,
+            &["--data", &level, "--data-urlencode", &answer],
+        )
+        .map_err(SubmitError::Transport)?;
+        verdict(&page)
+    }
+}
+
+/// The verdict in a response page of the puzzle site.
+fn verdict(page: &str) -> Result<Verdict, SubmitError> {
+    if page.contains("That's the right answer") {
+        Ok(Verdict::Correct)
+    } else if page.contains("answer is too high") {
+        Ok(Verdict::TooHigh)
+    } else if page.contains("answer is too low") {
+        Ok(Verdict::TooLow)
+    } else if page.contains("That's not the right answer") {
+        Ok(Verdict::Wrong)
+    } else if page.contains("You gave an answer too recently") {
+        // "You have 42s left to wait."
+        let wait = page
+            .find("You have ")
+            .and_then(|start| {
+                let rest = &page[start + "You have ".len()..];
+                rest.find(" left to wait").map(|end| &rest[..end])
+            })
+            .map_or("wait a while".into(), |wait| format!("wait {}", wait));
+        Err(SubmitError::TooSoon(wait))
+    } else if page.contains("Did you already complete it") {
+        Err(SubmitError::AlreadySolved)
+    } else {
+        Err(SubmitError::Transport("unrecognised response".into()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub year: u32,
+    pub day: u32,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+impl Entry {
+    fn parse(line: &str) -> Option<Entry> {
+        let mut fields = line.splitn(5, '\t');
+        Some(Entry {
+            year: fields.next()?.parse().ok()?,
+            day: fields.next()?.parse().ok()?,
+            part: fields.next()?.parse().ok()?,
+            verdict: fields.next()?.parse().ok()?,
+            answer: fields.next()?.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}",
+            self.year, self.day, self.part, self.verdict, self.answer
+        )
+    }
+}
+
+/// Why an answer is not worth sending, going by earlier verdicts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part is solved, with this answer.
+    Solved(String),
+    /// The same answer was judged before.
+    Judged(Verdict),
+    /// At or above this answer, which was too high.
+    AboveBound(String),
+    /// At or below this answer, which was too low.
+    BelowBound(String),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::Solved(answer) => write!(f, "already solved with {}", answer),
+            Refusal::Judged(verdict) => write!(f, "already submitted, {}", verdict),
+            Refusal::AboveBound(bound) => write!(f, "not below {}, which was too high", bound),
+            Refusal::BelowBound(bound) => write!(f, "not above {}, which was too low", bound),
+        }
+    }
+}
+
+/// Every answer submitted so far, one tab-separated line each in a file:
+/// year, day, part, verdict and answer.
+pub struct Ledger {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Ledger {
+    pub const DEFAULT_PATH: &str = "assets/answers.tsv";
+
+    /// The ledger in `path`, empty if the file does not exist yet.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, SubmitError> {
+        let path = path.into();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(SubmitError::Io(path, e)),
+        };
+
+        let entries = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty())
+            .map(|(i, line)| {
+                Entry::parse(line).ok_or_else(|| SubmitError::Ledger {
+                    path: path.clone(),
+                    line: i + 1,
+                })
+            })
+            .collect::<Result<Vec<Entry>, SubmitError>>()?;
+
+        Ok(Ledger { path, entries })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Whether `answer` is worth sending for the part.
+    pub fn check(&self, year: u32, day: u32, part: u8, answer: &str) -> Result<(), Refusal> {
+        let number = answer.parse::<BigInt>().ok();
+
+        for entry in &self.entries {
+            if (entry.year, entry.day, entry.part) != (year, day, part) {
+                continue;
+            }
+            if entry.verdict == Verdict::Correct {
+                return Err(Refusal::Solved(entry.answer.clone()));
+            }
+            if entry.answer == answer {
+                return Err(Refusal::Judged(entry.verdict));
+            }
+
+            let Some((number, bound)) = number.as_ref().zip(entry.answer.parse::<BigInt>().ok())
+            else {
+                continue;
+            };
+            match entry.verdict {
+                Verdict::TooHigh if *number >= bound => {
+                    return Err(Refusal::AboveBound(entry.answer.clone()));
+                }
+                Verdict::TooLow if *number <= bound => {
+                    return Err(Refusal::BelowBound(entry.answer.clone()));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Append `entry` to the ledger and its file.
+    pub fn record(&mut self, entry: Entry) -> Result<(), SubmitError> {
+        let io_error = |e| SubmitError::Io(self.path.clone(), e);
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(io_error)?;
+        writeln!(file, "{}", entry).map_err(io_error)?;
+
+        self.entries.push(entry);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Submission {
+    /// Not sent, the ledger already tells.
+    Refused(Refusal),
+    Judged(Verdict),
+}
+
+/// Send `answer` through `transport` unless the ledger refuses it, and record
+/// the verdict. An answer the site would not judge is not recorded, so it can
+/// be sent again.
+pub fn submit(
+    ledger: &mut Ledger,
+    transport: &dyn Transport,
+    year: u32,
+    day: u32,
+    part: u8,
+    answer: &str,
+) -> Result<Submission, SubmitError> {
+    if let Err(refusal) = ledger.check(year, day, part, answer) {
+        return Ok(Submission::Refused(refusal));
+    }
+
+    let verdict = transport.submit(year, day, part, answer)?;
+    ledger.record(Entry {
+        year,
+        day,
+        part,
+        answer: answer.to_string(),
+        verdict,
+    })?;
+
+    Ok(Submission::Judged(verdict))
+}
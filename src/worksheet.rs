@@ -0,0 +1,398 @@
+//! Cephalopod math worksheets (2025 day 6).
+//!
+//! A worksheet is a block of text where each problem occupies a range of
+//! columns: the operand rows on top and the operator on the last line.
+//! Problems are separated by columns that are blank on every operand row; an
+//! operator starts under its problem but may be wider than it.
+
+use std::{collections::HashMap, fmt};
+
+use crate::parser::{complete, grid};
+
+/// Which way the digits of a problem are read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reading {
+    Rows,
+    Columns,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// Operand rows of the problem, padded with spaces to the same width.
+    pub block: Vec<Vec<char>>,
+    pub operator: String,
+}
+
+impl Problem {
+    pub fn numbers(&self, reading: Reading) -> Result<Vec<i128>, String> {
+        match reading {
+            Reading::Rows => self.row_numbers(),
+            Reading::Columns => self.column_numbers(),
+        }
+    }
+
+    /// Numbers read the human way, one per row.
+    pub fn row_numbers(&self) -> Result<Vec<i128>, String> {
+        self.block
+            .iter()
+            .filter(|row| row.iter().any(|ch| *ch != ' '))
+            .map(|row| parse_digits(row.iter().filter(|ch| **ch != ' ')))
+            .collect()
+    }
+
+    /// Numbers read the cephalopod way: one per column, right to left, with
+    /// the most significant digit at the top.
+    pub fn column_numbers(&self) -> Result<Vec<i128>, String> {
+        let width = self.block.first().map_or(0, |row| row.len());
+
+        (0..width)
+            .rev()
+            .map(|col_i| {
+                parse_digits(
+                    self.block
+                        .iter()
+                        .map(|row| &row[col_i])
+                        .filter(|ch| **ch != ' '),
+                )
+            })
+            .collect()
+    }
+}
+
+fn parse_digits<'a>(digits: impl Iterator<Item = &'a char>) -> Result<i128, String> {
+    let number = digits.collect::<String>();
+    number
+        .parse::<i128>()
+        .map_err(|e| format!("Invalid number '{}': {}", number, e))
+}
+
+/// Split a worksheet into its problems, from left to right.
+pub fn parse(contents: &str) -> Result<Vec<Problem>, String> {
+    let mut grid = complete(grid("a character", Some), contents).map_err(|e| e.to_string())?;
+    while grid
+        .last()
+        .is_some_and(|row| row.iter().all(|ch| *ch == ' '))
+    {
+        grid.pop();
+    }
+
+    if grid.len() < 2 {
+        return Err("Worksheet needs at least one operand row and an operator row".into());
+    }
+
+    let width = grid.iter().map(|row| row.len()).max().unwrap();
+    for row in &mut grid {
+        row.resize(width, ' ');
+    }
+
+    let operator_row = grid.len() - 1;
+    let operands = &grid[..operator_row];
+
+    // Operators may be wider than their operands, so only the operand rows
+    // tell where problems start and end
+    let mut spans = vec![];
+    let mut start = None;
+    for col_i in 0..=width {
+        let separator = col_i == width || operands.iter().all(|row| row[col_i] == ' ');
+
+        match (start, separator) {
+            (None, false) => start = Some(col_i),
+            (Some(col_s), true) => {
+                spans.push(col_s..col_i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    let operators = &grid[operator_row];
+    let first = spans.first().map_or(width, |span| span.start);
+    if operators[..first].iter().any(|ch| *ch != ' ') {
+        return Err(format!(
+            "Operator before the first problem, in columns 0..{}",
+            first
+        ));
+    }
+
+    let mut problems = vec![];
+    for (i, span) in spans.iter().enumerate() {
+        // An operator starts under its problem and may run on to the next one
+        let end = spans.get(i + 1).map_or(width, |next| next.start);
+        let operator = operators[span.start..end]
+            .iter()
+            .collect::<String>()
+            .trim()
+            .to_string();
+        if operator.is_empty() {
+            return Err(format!(
+                "Missing operator for columns {}..{}",
+                span.start, span.end
+            ));
+        }
+        if operator.contains(' ') {
+            return Err(format!(
+                "More than one operator for columns {}..{}: '{}'",
+                span.start, span.end, operator
+            ));
+        }
+
+        problems.push(Problem {
+            block: operands
+                .iter()
+                .map(|row| row[span.clone()].to_vec())
+                .collect(),
+            operator,
+        });
+    }
+
+    Ok(problems)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EvalError {
+    Parse(String),
+    UnknownOperator(String),
+    NoOperands,
+    DivisionByZero,
+    Overflow,
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::Parse(e) => write!(f, "{}", e),
+            EvalError::UnknownOperator(op) => write!(f, "Unknown operator: {}", op),
+            EvalError::NoOperands => write!(f, "No operands"),
+            EvalError::DivisionByZero => write!(f, "Division by zero"),
+            EvalError::Overflow => write!(f, "Arithmetic overflow"),
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+pub type Operator = Box<dyn Fn(&[i128]) -> Result<i128, EvalError> + Send + Sync>;
+
+/// Operators known to the evaluator, keyed by the symbol on the worksheet.
+pub struct Operators {
+    operators: HashMap<String, Operator>,
+}
+
+fn fold(
+    operands: &[i128],
+    f: impl Fn(i128, i128) -> Result<i128, EvalError>,
+) -> Result<i128, EvalError> {
+    let (first, rest) = operands.split_first().ok_or(EvalError::NoOperands)?;
+    rest.iter().try_fold(*first, |acc, x| f(acc, *x))
+}
+
+/// `+`, `*`, `-`, `/`, `max` and `min`, all with checked arithmetic.
+/// `-` and `/` apply left to right.
+impl Default for Operators {
+    fn default() -> Self {
+        let mut operators = Operators::new();
+
+        operators.register("+", |xs| {
+            xs.iter()
+                .try_fold(0_i128, |acc, x| acc.checked_add(*x))
+                .ok_or(EvalError::Overflow)
+        });
+        operators.register("*", |xs| {
+            xs.iter()
+                .try_fold(1_i128, |acc, x| acc.checked_mul(*x))
+                .ok_or(EvalError::Overflow)
+        });
+        operators.register("-", |xs| {
+            fold(xs, |a, b| a.checked_sub(b).ok_or(EvalError::Overflow))
+        });
+        operators.register("/", |xs| {
+            fold(xs, |a, b| {
+                if b == 0 {
+                    return Err(EvalError::DivisionByZero);
+                }
+                a.checked_div(b).ok_or(EvalError::Overflow)
+            })
+        });
+        operators.register("max", |xs| {
+            xs.iter().max().copied().ok_or(EvalError::NoOperands)
+        });
+        operators.register("min", |xs| {
+            xs.iter().min().copied().ok_or(EvalError::NoOperands)
+        });
+
+        operators
+    }
+}
+
+impl Operators {
+    /// An empty registry, see [`Operators::default`] for the standard set.
+    pub fn new() -> Self {
+        Operators {
+            operators: HashMap::new(),
+        }
+    }
+
+    /// Register an operator, replacing any previous one with the same symbol.
+    pub fn register(
+        &mut self,
+        symbol: &str,
+        f: impl Fn(&[i128]) -> Result<i128, EvalError> + Send + Sync + 'static,
+    ) {
+        self.operators.insert(symbol.to_string(), Box::new(f));
+    }
+
+    pub fn apply(&self, symbol: &str, operands: &[i128]) -> Result<i128, EvalError> {
+        let operator = self
+            .operators
+            .get(symbol)
+            .ok_or_else(|| EvalError::UnknownOperator(symbol.to_string()))?;
+        operator(operands)
+    }
+}
+
+/// A single evaluated problem, kept for reporting.
+#[derive(Debug, Clone)]
+pub struct Evaluation {
+    pub operator: String,
+    pub operands: Vec<i128>,
+    pub value: Result<i128, EvalError>,
+}
+
+impl fmt::Display for Evaluation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operands = self
+            .operands
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>()
+            .join(&format!(" {} ", self.operator));
+        match &self.value {
+            Ok(value) => write!(f, "{} = {}", operands, value),
+            Err(e) => write!(f, "{} = error: {}", operands, e),
+        }
+    }
+}
+
+/// Evaluate every problem independently, so one failing problem does not
+/// hide the others.
+pub fn evaluate(problems: &[Problem], reading: Reading, operators: &Operators) -> Vec<Evaluation> {
+    problems
+        .iter()
+        .map(|problem| match problem.numbers(reading) {
+            Ok(operands) => Evaluation {
+                value: operators.apply(&problem.operator, &operands),
+                operator: problem.operator.clone(),
+                operands,
+            },
+            Err(e) => Evaluation {
+                operator: problem.operator.clone(),
+                operands: vec![],
+                value: Err(EvalError::Parse(e)),
+            },
+        })
+        .collect()
+}
+
+/// Grand total of the evaluations, failing on the first failed problem or
+/// if the total itself overflows.
+pub fn total(evaluations: &[Evaluation]) -> Result<i128, EvalError> {
+    evaluations.iter().try_fold(0_i128, |acc, evaluation| {
+        acc.checked_add(evaluation.value.clone()?)
+            .ok_or(EvalError::Overflow)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(contents: &str, reading: Reading) -> Vec<i128> {
+        let problems = parse(contents).unwrap();
+        evaluate(&problems, reading, &Operators::default())
+            .into_iter()
+            .map(|evaluation| evaluation.value.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn operators_may_be_wider_than_their_problem() {
+        let problems = parse("12 3\n 4 5\nmax min\n").unwrap();
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].operator, "max");
+        assert_eq!(problems[0].block, [vec!['1', '2'], vec![' ', '4']]);
+        assert_eq!(problems[1].operator, "min");
+        assert_eq!(problems[1].block, [vec!['3'], vec!['5']]);
+
+        assert_eq!(values("12 3\n 4 5\nmax min\n", Reading::Rows), [12, 3]);
+        assert_eq!(values("12 3\n 4 5\nmax min\n", Reading::Columns), [24, 35]);
+    }
+
+    #[test]
+    fn a_column_of_zeros_is_a_number() {
+        let problems = parse("10 3\n20 4\n*  +\n").unwrap();
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].column_numbers(), Ok(vec![0, 12]));
+
+        assert_eq!(values("10 3\n20 4\n*  +\n", Reading::Rows), [200, 7]);
+        assert_eq!(values("10 3\n20 4\n*  +\n", Reading::Columns), [0, 34]);
+    }
+
+    #[test]
+    fn reads_the_example() {
+        let example = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  \n";
+        assert_eq!(values(example, Reading::Rows), [33210, 490, 4243455, 401]);
+        assert_eq!(
+            values(example, Reading::Columns),
+            [8544, 625, 3253600, 1058]
+        );
+    }
+
+    #[test]
+    fn subtraction_and_division_apply_left_to_right() {
+        let operators = Operators::default();
+        assert_eq!(operators.apply("-", &[10, 3, 2]), Ok(5));
+        assert_eq!(operators.apply("/", &[100, 5, 2]), Ok(10));
+        assert_eq!(values("20 100\n 3   5\n-  /  \n", Reading::Rows), [17, 20]);
+    }
+
+    #[test]
+    fn division_by_zero_is_an_error() {
+        let operators = Operators::default();
+        assert_eq!(
+            operators.apply("/", &[7, 0]),
+            Err(EvalError::DivisionByZero)
+        );
+        assert_eq!(operators.apply("/", &[0, 7]), Ok(0));
+    }
+
+    #[test]
+    fn overflow_fails_only_its_problem() {
+        let max = i128::MAX.to_string();
+        let pad = " ".repeat(max.len() - 1);
+        let problems = parse(&format!("{max} 1\n1{pad} 2\n+{pad} +\n")).unwrap();
+        let evaluations = evaluate(&problems, Reading::Rows, &Operators::default());
+
+        assert_eq!(evaluations[0].value, Err(EvalError::Overflow));
+        assert_eq!(evaluations[1].value, Ok(3));
+        assert_eq!(total(&evaluations), Err(EvalError::Overflow));
+    }
+
+    #[test]
+    fn a_registered_operator_replaces_the_default() {
+        let mut operators = Operators::default();
+        operators.register("+", |xs| Ok(xs.len() as i128));
+        assert_eq!(operators.apply("+", &[5, 6, 7]), Ok(3));
+        assert_eq!(operators.apply("*", &[5, 6, 7]), Ok(210));
+        assert_eq!(
+            operators.apply("^", &[2, 3]),
+            Err(EvalError::UnknownOperator("^".into()))
+        );
+    }
+
+    #[test]
+    fn rejects_misplaced_operators() {
+        assert!(parse("1 2\n+\n").is_err());
+        assert!(parse(" 1\n+ \n").is_err());
+        assert!(parse("12\n+ *\n").is_err());
+    }
+}
@@ -0,0 +1,35 @@
+//! Every registered cross-check on a few small random inputs, like
+//! `main check` with a fixed seed.
+
+use aoc::{check::Failure, registry};
+
+const CASES: usize = 20;
+const SEED: u64 = 2025;
+
+#[test]
+fn every_cross_check_agrees() {
+    let mut failures = vec![];
+
+    for (year, day, solution) in registry::all() {
+        for cross_check in solution.cross_checks() {
+            match cross_check.run(CASES, SEED) {
+                Ok(_) => {}
+                Err(Failure::NothingCompared) => failures.push(format!(
+                    "{} day {} {}: the reference rejected every input",
+                    year, day, cross_check.name
+                )),
+                Err(Failure::Mismatch(mismatch)) => failures.push(format!(
+                    "{} day {} {}:\n{}reference: {:?}\noptimised: {:?}",
+                    year,
+                    day,
+                    cross_check.name,
+                    mismatch.input,
+                    mismatch.reference,
+                    mismatch.optimised
+                )),
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
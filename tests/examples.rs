@@ -0,0 +1,71 @@
+//! One test per inline example of every registered solution.
+//!
+//! Examples are discovered through the registry at run time, so this uses its
+//! own harness instead of `#[test]` functions. Arguments that are not flags
+//! filter the tests by name, like the default harness.
+
+use std::process::ExitCode;
+
+use aoc::{registry, solution::Check};
+
+fn main() -> ExitCode {
+    let filters = std::env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with('-'))
+        .collect::<Vec<String>>();
+
+    let mut passed = 0;
+    let mut failed = vec![];
+    let mut ignored = 0;
+
+    for (year, day, solution) in registry::all() {
+        for example in solution.examples() {
+            for part in [1, 2] {
+                let name = format!("{}::day_{}::{}::part_{}", year, day, example.name, part);
+                if !filters.is_empty() && !filters.iter().any(|f| name.contains(f.as_str())) {
+                    continue;
+                }
+
+                match example.check(solution, part) {
+                    Check::Passed(_) => {
+                        println!("test {} ... ok", name);
+                        passed += 1;
+                    }
+                    Check::Skipped => {
+                        println!("test {} ... ignored", name);
+                        ignored += 1;
+                    }
+                    Check::Failed { expected, actual } => {
+                        println!("test {} ... FAILED", name);
+                        let actual = match actual {
+                            Ok(answer) => answer.to_string(),
+                            Err(e) => format!("error: {}", e),
+                        };
+                        failed.push(format!("{}: expected {}, got {}", name, expected, actual));
+                    }
+                }
+            }
+        }
+    }
+
+    if !failed.is_empty() {
+        println!("\nfailures:");
+        for failure in &failed {
+            println!("    {}", failure);
+        }
+    }
+
+    println!(
+        "\ntest result: {}. {} passed; {} failed; {} ignored",
+        if failed.is_empty() { "ok" } else { "FAILED" },
+        passed,
+        failed.len(),
+        ignored
+    );
+
+    if failed.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,32 @@
+//! Every registered generator gives input its day's parser accepts, down to
+//! size 0.
+
+use aoc::{registry, rng::Rng};
+
+const SEEDS: [u64; 3] = [1, 2, 2025];
+const SIZES: [usize; 4] = [0, 1, 2, 10];
+
+#[test]
+fn generated_inputs_solve() {
+    let mut failures = vec![];
+
+    for (year, day, solution) in registry::all() {
+        let Some(generator) = solution.generator() else {
+            continue;
+        };
+
+        for seed in SEEDS {
+            for size in SIZES {
+                let input = generator(&mut Rng::new(seed), size);
+                if let Err(e) = solution.part_1(&input) {
+                    failures.push(format!(
+                        "{} day {} (seed {}, size {}): {}\n{}",
+                        year, day, seed, size, e, input
+                    ));
+                }
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
@@ -0,0 +1,65 @@
+//! Loading inputs through the cache, with a mirror directory as the source.
+
+use std::{fs, path::PathBuf};
+
+use aoc::{
+    inputs::{InputError, Inputs, Mirror},
+    normalise::Normalisation,
+};
+
+/// An empty directory of its own for each test.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-inputs-{}-{}", std::process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn fetches_a_missing_input_once_and_caches_it() {
+    let dir = temp_dir("mirror");
+    let mirror = dir.join("mirror");
+    let assets = dir.join("assets");
+    fs::create_dir_all(mirror.join("2025")).unwrap();
+    fs::write(mirror.join("2025/day_3.input"), "\u{feff}123\r\n456  \r\n").unwrap();
+
+    let inputs = Inputs::new(&assets).with_source(Mirror::new(&mirror));
+    let cached = assets.join("2025/day_3.input");
+    assert_eq!(inputs.path(2025, 3), cached);
+    assert!(!cached.exists());
+
+    // Fetched from the mirror, and cached as fetched
+    assert_eq!(inputs.load(2025, 3).unwrap(), "\u{feff}123\r\n456  \r\n");
+    assert_eq!(
+        fs::read_to_string(&cached).unwrap(),
+        "\u{feff}123\r\n456  \r\n"
+    );
+
+    // The cache now wins over the mirror
+    fs::write(mirror.join("2025/day_3.input"), "changed\n").unwrap();
+    assert_eq!(
+        inputs.read(2025, 3, Normalisation::Full).unwrap(),
+        "123\n456\n"
+    );
+    fs::remove_dir_all(&mirror).unwrap();
+    assert_eq!(inputs.load(2025, 3).unwrap(), "\u{feff}123\r\n456  \r\n");
+
+    // Neither cached nor mirrored
+    assert!(matches!(inputs.load(2025, 4), Err(InputError::Io(..))));
+    assert!(!assets.join("2025/day_4.input").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn missing_input_without_a_source() {
+    let dir = temp_dir("missing");
+    let inputs = Inputs::new(dir.join("assets"));
+
+    match inputs.load(2025, 1) {
+        Err(InputError::Missing(path)) => assert_eq!(path, dir.join("assets/2025/day_1.input")),
+        other => panic!("expected a missing input, got {:?}", other),
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
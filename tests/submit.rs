@@ -0,0 +1,250 @@
+//! The submission workflow against a fake site: the ledger's refusals, and
+//! the HTTP transport talking to a local server.
+
+use std::{
+    cell::RefCell,
+    io::{Read, Write},
+    net::TcpListener,
+    path::PathBuf,
+    thread,
+};
+
+use aoc::submit::{self, Http, Ledger, Refusal, Submission, SubmitError, Transport, Verdict};
+
+/// A site whose answer is `answer` for every part, remembering what it got.
+struct FakeSite {
+    answer: i64,
+    received: RefCell<Vec<String>>,
+}
+
+impl FakeSite {
+    fn new(answer: i64) -> Self {
+        FakeSite {
+            answer,
+            received: RefCell::new(vec![]),
+        }
+    }
+}
+
+impl Transport for FakeSite {
+    fn submit(
+        &self,
+        _year: u32,
+        _day: u32,
+        _part: u8,
+        answer: &str,
+    ) -> Result<Verdict, SubmitError> {
+        self.received.borrow_mut().push(answer.to_string());
+        match answer.parse::<i64>() {
+            Ok(n) if n > self.answer => Ok(Verdict::TooHigh),
+            Ok(n) if n < self.answer => Ok(Verdict::TooLow),
+            Ok(_) => Ok(Verdict::Correct),
+            Err(_) => Ok(Verdict::Wrong),
+        }
+    }
+}
+
+/// A ledger file of its own for each test.
+fn ledger_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("aoc-ledger-{}-{}.tsv", std::process::id(), name));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn learns_bounds_and_stops_after_the_right_answer() {
+    let path = ledger_path("bounds");
+    let site = FakeSite::new(50);
+    let mut ledger = Ledger::open(&path).unwrap();
+    let send =
+        |ledger: &mut Ledger, answer| submit::submit(ledger, &site, 2025, 1, 1, answer).unwrap();
+
+    assert_eq!(
+        send(&mut ledger, "80"),
+        Submission::Judged(Verdict::TooHigh)
+    );
+    assert_eq!(send(&mut ledger, "20"), Submission::Judged(Verdict::TooLow));
+    assert_eq!(send(&mut ledger, "abc"), Submission::Judged(Verdict::Wrong));
+
+    assert_eq!(
+        send(&mut ledger, "90"),
+        Submission::Refused(Refusal::AboveBound("80".into()))
+    );
+    assert_eq!(
+        send(&mut ledger, "80"),
+        Submission::Refused(Refusal::Judged(Verdict::TooHigh))
+    );
+    assert_eq!(
+        send(&mut ledger, "-5"),
+        Submission::Refused(Refusal::BelowBound("20".into()))
+    );
+    assert_eq!(
+        send(&mut ledger, "abc"),
+        Submission::Refused(Refusal::Judged(Verdict::Wrong))
+    );
+
+    assert_eq!(
+        send(&mut ledger, "50"),
+        Submission::Judged(Verdict::Correct)
+    );
+    assert_eq!(
+        send(&mut ledger, "51"),
+        Submission::Refused(Refusal::Solved("50".into()))
+    );
+
+    // Only the answers worth sending reached the site
+    assert_eq!(*site.received.borrow(), ["80", "20", "abc", "50"]);
+
+    // The other part is untouched
+    assert_eq!(ledger.check(2025, 1, 2, "90"), Ok(()));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn ledger_is_kept_between_runs() {
+    let path = ledger_path("reopen");
+    let site = FakeSite::new(7);
+
+    let mut ledger = Ledger::open(&path).unwrap();
+    submit::submit(&mut ledger, &site, 2025, 10, 2, "1000").unwrap();
+
+    let ledger = Ledger::open(&path).unwrap();
+    assert_eq!(ledger.entries().len(), 1);
+    assert_eq!(
+        ledger.check(2025, 10, 2, "2000"),
+        Err(Refusal::AboveBound("1000".into()))
+    );
+
+    std::fs::write(&path, "2025\t10\t2\tmaybe\t1000\n").unwrap();
+    assert!(matches!(
+        Ledger::open(&path),
+        Err(SubmitError::Ledger { line: 1, .. })
+    ));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// A site where part 2 opens only once part 1 is solved, answering both
+/// with `answer`.
+struct LockedSite {
+    answer: &'static str,
+    unlocked: RefCell<bool>,
+}
+
+impl Transport for LockedSite {
+    fn submit(
+        &self,
+        _year: u32,
+        _day: u32,
+        part: u8,
+        answer: &str,
+    ) -> Result<Verdict, SubmitError> {
+        if part == 2 && !*self.unlocked.borrow() {
+            return Err(SubmitError::AlreadySolved);
+        }
+        if answer != self.answer {
+            return Ok(Verdict::Wrong);
+        }
+        *self.unlocked.borrow_mut() = true;
+        Ok(Verdict::Correct)
+    }
+}
+
+#[test]
+fn locked_part_2_can_be_submitted_once_unlocked() {
+    let path = ledger_path("locked");
+    let site = LockedSite {
+        answer: "42",
+        unlocked: RefCell::new(false),
+    };
+    let mut ledger = Ledger::open(&path).unwrap();
+
+    assert!(matches!(
+        submit::submit(&mut ledger, &site, 2025, 4, 2, "42"),
+        Err(SubmitError::AlreadySolved)
+    ));
+    assert!(ledger.entries().is_empty());
+    assert_eq!(ledger.check(2025, 4, 2, "42"), Ok(()));
+
+    assert_eq!(
+        submit::submit(&mut ledger, &site, 2025, 4, 1, "42").unwrap(),
+        Submission::Judged(Verdict::Correct)
+    );
+    assert_eq!(
+        submit::submit(&mut ledger, &site, 2025, 4, 2, "42").unwrap(),
+        Submission::Judged(Verdict::Correct)
+    );
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// Serve `page` to one request on a local port, returning the base URL and
+/// the request as received.
+fn serve_once(page: &'static str) -> (String, thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = vec![];
+        let mut buffer = [0; 1024];
+        // Headers, then as much body as they announce
+        loop {
+            let n = stream.read(&mut buffer).unwrap();
+            request.extend_from_slice(&buffer[..n]);
+            let text = String::from_utf8_lossy(&request);
+            if let Some(end) = text.find("\r\n\r\n") {
+                let length = text[..end]
+                    .lines()
+                    .find_map(|line| line.strip_prefix("Content-Length: "))
+                    .map_or(0, |length| length.trim().parse().unwrap());
+                if request.len() >= end + 4 + length {
+                    break;
+                }
+            }
+            if n == 0 {
+                break;
+            }
+        }
+
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            page.len(),
+            page
+        )
+        .unwrap();
+        String::from_utf8(request).unwrap()
+    });
+
+    (url, server)
+}
+
+#[test]
+fn http_transport_posts_the_answer_and_reads_the_verdict() {
+    let (url, server) = serve_once(
+        "<article><p>That's not the right answer; your answer is too low.</p></article>",
+    );
+
+    let verdict = Http::new(&url, "secret").submit(2025, 3, 2, "1 2");
+    let request = server.join().unwrap();
+
+    assert!(matches!(verdict, Ok(Verdict::TooLow)));
+    assert!(request.starts_with("POST /2025/day/3/answer "));
+    assert!(request.contains("Cookie: session=secret"));
+    assert!(request.ends_with("level=2&answer=1+2"), "{}", request);
+}
+
+#[test]
+fn http_transport_reports_rate_limiting() {
+    let (url, server) = serve_once(
+        "<p>You gave an answer too recently; you have to wait after submitting an answer \
+         before trying again. You have 42s left to wait.</p>",
+    );
+
+    let verdict = Http::new(&url, "secret").submit(2025, 3, 1, "5");
+    server.join().unwrap();
+
+    assert!(matches!(verdict, Err(SubmitError::TooSoon(wait)) if wait == "wait 42s"));
+}